@@ -0,0 +1,48 @@
+//! Measures the cost of a single step of the headless simulation, for growing numbers of
+//! topplegrass. Run with `cargo bench`; criterion reports the time per step for each population
+//! size, and the change compared to the previous run.
+//! Every iteration steps a freshly populated simulation with the same seed, so each measures the
+//! same step of the same population.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use evolution_island::{
+    resources::{topplegrass::TopplegrassConfig, world_bounds::WorldBounds},
+    utils::headless::HeadlessSimulation,
+};
+
+const FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// A HeadlessSimulation holding the given number of topplegrass, which have been given the
+/// components of their prefab and are ready to roll.
+fn populated_simulation(population: u32) -> HeadlessSimulation {
+    let bounds = WorldBounds::new(-100.0, 100.0, -100.0, 100.0);
+    let mut simulation = HeadlessSimulation::with_bounds(bounds);
+    simulation.world.insert(TopplegrassConfig {
+        initial_population: population,
+        ..Default::default()
+    });
+    simulation.step(FRAME_TIME);
+    simulation
+}
+
+fn headless_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("headless_step");
+    for population in [100, 1000, 10000].iter() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(population),
+            population,
+            |b, &population| {
+                b.iter_batched_ref(
+                    || populated_simulation(population),
+                    |simulation| simulation.step(FRAME_TIME),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, headless_step);
+criterion_main!(benches);
@@ -15,3 +15,15 @@ pub struct Circle {
 impl Component for Circle {
     type Storage = DenseVecStorage<Self>;
 }
+
+/// An immovable obstacle that moving entities can't pass through. They are pushed back out by
+/// the ObstacleCollisionSystem and slide along its edge instead.
+#[derive(Clone, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub struct Obstacle {
+    pub collider: Circle,
+}
+
+impl Component for Obstacle {
+    type Storage = DenseVecStorage<Self>;
+}
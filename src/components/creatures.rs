@@ -11,7 +11,9 @@ use amethyst::{
 use serde::{Deserialize, Serialize};
 
 use crate::components::{
-    collider::Circle, combat::CombatPrefabData, digestion::DigestionPrefabData,
+    collider::{Circle, Obstacle},
+    combat::CombatPrefabData,
+    digestion::DigestionPrefabData,
     perception::Perception,
 };
 
@@ -62,6 +64,23 @@ impl Component for FallingTag {
     type Storage = NullStorage<Self>;
 }
 
+/// Topplegrass with this tag has come to rest on the ground: it no longer rolls, wobbles, jumps or
+/// gets carried along by the wind, until a strong enough gust sets it loose again.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RestingTag;
+
+impl Component for RestingTag {
+    type Storage = NullStorage<Self>;
+}
+
+/// Freshly spawned topplegrass that hasn't been given its initial velocity yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpawnVelocityTag;
+
+impl Component for SpawnVelocityTag {
+    type Storage = NullStorage<Self>;
+}
+
 /// Entities tagged with this Component will despawn as soon as their position is outside the world bounds.
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
@@ -71,6 +90,15 @@ impl Component for DespawnWhenOutOfBoundsTag {
     type Storage = NullStorage<Self>;
 }
 
+/// Entities with this Component bounce off the world bounds instead of crossing them.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub struct BounceBounds;
+
+impl Component for BounceBounds {
+    type Storage = NullStorage<Self>;
+}
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
 pub struct IntelligenceTag;
@@ -92,6 +120,57 @@ impl Component for Movement {
     type Storage = DenseVecStorage<Self>;
 }
 
+/// The size of a creature. The SizeSystem keeps both the scale of the Transform and the radius of
+/// the collider Circle in sync with this value.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub struct Size {
+    pub value: f32,
+}
+impl Component for Size {
+    type Storage = DenseVecStorage<Self>;
+}
+
+/// Makes a creature grow over time. Its Size increases by rate per second until it reaches max_size.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub struct Growth {
+    pub rate: f32,
+    pub max_size: f32,
+}
+impl Component for Growth {
+    type Storage = DenseVecStorage<Self>;
+}
+
+/// Entities with a DragProfile are pushed along by the wind rather than moving at exactly the wind
+/// velocity. The larger the cross-sectional area, the faster they catch up with the wind.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+pub struct DragProfile {
+    pub area: f32,
+}
+impl Component for DragProfile {
+    type Storage = DenseVecStorage<Self>;
+}
+
+/// Lets a creature remember where it last saw a predator, so it keeps fleeing from there for
+/// `duration` seconds after losing sight of it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PrefabData)]
+#[prefab(Component)]
+#[serde(deny_unknown_fields)]
+pub struct ThreatMemory {
+    pub duration: f32,
+    /// Where the closest predator was last seen, until the creature forgets about it.
+    #[serde(skip)]
+    pub last_seen: Option<Vector3<f32>>,
+    /// Seconds until the creature forgets about the last seen predator.
+    #[serde(skip)]
+    pub timer: f32,
+}
+impl Component for ThreatMemory {
+    type Storage = DenseVecStorage<Self>;
+}
+
 ///
 ///
 ///
@@ -115,16 +194,32 @@ impl Wander {
     }
 }
 
+/// Makes a creature leave a carcass of the given creature type behind when it dies.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PrefabData)]
 #[prefab(Component)]
 pub struct Carcass {
     pub creature_type: CreatureType,
+    /// Nutrition the carcass carries per unit of Size of the deceased creature. Without it, the
+    /// carcass only has the nutrition its own prefab gives it.
+    #[serde(default)]
+    pub energy_per_size: Option<f32>,
+    /// Seconds until the carcass has decayed and is removed.
+    #[serde(default)]
+    pub decay_time: Option<f32>,
 }
 
 impl Component for Carcass {
     type Storage = DenseVecStorage<Self>;
 }
 
+/// The type of creature an entity was spawned as, attached by the CreatureSpawnerSystem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Species(pub CreatureType);
+
+impl Component for Species {
+    type Storage = DenseVecStorage<Self>;
+}
+
 // This is the main prefab data for creatures.
 // It defines all the components that a creature could have.
 // In the prefab, it is not necessary to define all of them (due to Option).
@@ -138,7 +233,9 @@ pub struct CreaturePrefabData {
     gltf: Option<AssetPrefab<GltfSceneAsset, GltfSceneFormat>>,
     movement: Option<Movement>,
     wander: Option<Wander>,
+    threat_memory: Option<ThreatMemory>,
     collider: Option<Circle>,
+    obstacle: Option<Obstacle>,
     digestion: Option<DigestionPrefabData>,
     combat: Option<CombatPrefabData>,
     intelligence_tag: Option<IntelligenceTag>,
@@ -147,6 +244,10 @@ pub struct CreaturePrefabData {
     carcass: Option<Carcass>,
     avoid_obstacles_tag: Option<AvoidObstaclesTag>,
     despawn_when_out_of_bounds_tag: Option<DespawnWhenOutOfBoundsTag>,
+    bounce_bounds: Option<BounceBounds>,
     topplegrass_tag: Option<TopplegrassTag>,
     falling_tag: Option<FallingTag>,
+    drag_profile: Option<DragProfile>,
+    size: Option<Size>,
+    growth: Option<Growth>,
 }
@@ -0,0 +1,19 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Component, DenseVecStorage},
+};
+
+/// A static wind emitter placed by the player. It pushes entities within radius of its position
+/// in its direction, on top of the global wind.
+#[derive(Clone, Debug)]
+pub struct Fan {
+    pub position: Vector2<f32>,
+    pub direction: Vector2<f32>,
+    /// Acceleration in units per second per second, right at the fan.
+    pub strength: f32,
+    pub radius: f32,
+}
+
+impl Component for Fan {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,13 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Tracks how long a topplegrass has been rolling along the ground since it last jumped. The
+/// longer it has been grounded, the more likely it is to jump.
+#[derive(Clone, Debug, Default)]
+pub struct GroundedTimer {
+    /// Time in seconds since the topplegrass last landed, or since it spawned.
+    pub grounded_time: f32,
+}
+
+impl Component for GroundedTimer {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,13 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Tracks how long an entity has been (nearly) standing still. Used by the IdleDespawnSystem to
+/// clean up topplegrass that got stuck when the wind died down.
+#[derive(Clone, Debug, Default)]
+pub struct IdleTimer {
+    /// Time in seconds the entity has continuously been below the idle speed threshold.
+    pub idle_seconds: f32,
+}
+
+impl Component for IdleTimer {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,18 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Entities with a Lifetime are deleted by the LifetimeSystem once their remaining time runs out.
+#[derive(Clone, Debug, Default)]
+pub struct Lifetime {
+    /// Remaining time in seconds.
+    pub remaining: f32,
+}
+
+impl Lifetime {
+    pub fn new(seconds: f32) -> Lifetime {
+        Lifetime { remaining: seconds }
+    }
+}
+
+impl Component for Lifetime {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -1 +1,13 @@
+pub mod fan;
+pub mod grounded_timer;
+pub mod idle_timer;
+pub mod lifetime;
 pub mod perception;
+pub mod pinned;
+pub mod scent;
+pub mod seed;
+pub mod spawn_animation;
+pub mod topplegrass_variant;
+pub mod wind_migration;
+pub mod wind_shadow;
+pub mod wobble;
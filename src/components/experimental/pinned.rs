@@ -0,0 +1,11 @@
+use amethyst::ecs::{Component, NullStorage};
+
+/// Keeps an entity in place, for reproducing rotation and other visual problems. The
+/// MovementSystem and GravitySystem don't move pinned entities, but pinned topplegrass still
+/// rolls as if it moved at TopplegrassConfig::pinned_roll_velocity.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Pinned;
+
+impl Component for Pinned {
+    type Storage = NullStorage<Self>;
+}
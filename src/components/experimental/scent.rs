@@ -0,0 +1,12 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Entities with a ScentEmitter leave a trail of scent in the ScentGrid wherever they go.
+#[derive(Clone, Debug, Default)]
+pub struct ScentEmitter {
+    /// Amount of scent deposited per second.
+    pub strength: f32,
+}
+
+impl Component for ScentEmitter {
+    type Storage = DenseVecStorage<Self>;
+}
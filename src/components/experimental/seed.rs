@@ -0,0 +1,19 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// A seed dropped by topplegrass. The GerminationSystem turns it into a plant once its time has
+/// run out.
+#[derive(Clone, Debug, Default)]
+pub struct Seed {
+    /// Time in seconds until the seed germinates.
+    pub time_to_germinate: f32,
+}
+
+impl Seed {
+    pub fn new(time_to_germinate: f32) -> Seed {
+        Seed { time_to_germinate }
+    }
+}
+
+impl Component for Seed {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,36 @@
+use amethyst::{
+    core::math::Vector3,
+    ecs::{Component, DenseVecStorage},
+};
+
+/// Makes a freshly spawned entity grow from nothing to its intended scale, rather than popping
+/// into existence at full size. Removed by the SpawnAnimationSystem once the animation is done.
+#[derive(Clone, Debug)]
+pub struct SpawnAnimation {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub target_scale: Vector3<f32>,
+}
+
+impl SpawnAnimation {
+    pub fn new(duration: f32, target_scale: Vector3<f32>) -> SpawnAnimation {
+        SpawnAnimation {
+            elapsed: 0.0,
+            duration,
+            target_scale,
+        }
+    }
+
+    /// Returns how far along the animation is, from 0.0 (just spawned) to 1.0 (done).
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+}
+
+impl Component for SpawnAnimation {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,10 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Which of the TopplegrassConfig::variants a topplegrass was spawned as, by index.
+/// Topplegrass without one is of the standard size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TopplegrassVariantId(pub usize);
+
+impl Component for TopplegrassVariantId {
+    type Storage = DenseVecStorage<Self>;
+}
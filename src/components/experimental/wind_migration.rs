@@ -0,0 +1,14 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// Entities with WindMigration steer partly along the wind direction, so populations slowly drift
+/// across the map.
+#[derive(Clone, Debug, Default)]
+pub struct WindMigration {
+    /// How much the wind direction dominates the entity's own heading.
+    /// 0.0 ignores the wind entirely, 1.0 follows the wind exactly.
+    pub strength: f32,
+}
+
+impl Component for WindMigration {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,19 @@
+use amethyst::ecs::{Component, DenseVecStorage};
+
+/// How much of the wind reaches an entity in the lee of an obstacle, as computed by the
+/// WindShadowSystem. The wind sampled at the entity's position is multiplied by the factor.
+#[derive(Clone, Debug)]
+pub struct WindShadow {
+    /// 1.0 in the open, lower when sheltered behind an obstacle.
+    pub factor: f32,
+}
+
+impl Default for WindShadow {
+    fn default() -> Self {
+        WindShadow { factor: 1.0 }
+    }
+}
+
+impl Component for WindShadow {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,27 @@
+use amethyst::{
+    core::math::Vector2,
+    ecs::{Component, DenseVecStorage},
+};
+
+/// Makes a rolling topplegrass wobble from side to side, so its path meanders instead of following
+/// a perfectly straight line. Every entity has its own phase, so they don't wobble in unison.
+#[derive(Clone, Debug)]
+pub struct Wobble {
+    /// Current phase of the wobble in radians.
+    pub phase: f32,
+    /// The sideways velocity added to the entity by the wobble during the last frame.
+    pub offset: Vector2<f32>,
+}
+
+impl Wobble {
+    pub fn new(phase: f32) -> Wobble {
+        Wobble {
+            phase,
+            offset: Vector2::zeros(),
+        }
+    }
+}
+
+impl Component for Wobble {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate log;
+
+pub mod components;
+pub mod render_graph;
+pub mod resources;
+pub mod states;
+pub mod systems;
+pub mod utils;
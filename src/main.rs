@@ -1,6 +1,3 @@
-#[macro_use]
-extern crate log;
-
 use amethyst::assets::PrefabLoaderSystemDesc;
 use amethyst::{
     audio::{AudioBundle, DjSystem},
@@ -18,16 +15,9 @@ use amethyst::{
 use amethyst::renderer::plugins::{RenderPbr3D, RenderToWindow};
 use amethyst::renderer::RenderingBundle;
 
-mod components;
-mod render_graph;
-mod resources;
-mod states;
-mod systems;
-mod utils;
-
-use crate::components::{combat, creatures};
-use crate::resources::audio::Music;
-use crate::states::loading::LoadingState;
+use evolution_island::components::{combat, creatures};
+use evolution_island::resources::audio::Music;
+use evolution_island::states::loading::LoadingState;
 
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
@@ -2,7 +2,8 @@ use amethyst::audio::output::init_output;
 use amethyst::{
     assets::Loader,
     audio::{AudioSink, OggFormat, SourceHandle},
-    ecs::prelude::{World, WorldExt},
+    core::math::Vector3,
+    ecs::prelude::{Entity, World, WorldExt},
 };
 
 use std::iter::Cycle;
@@ -39,3 +40,50 @@ pub fn initialise_audio(world: &mut World) {
     // Add sounds to the world
     world.insert(music);
 }
+
+/// Plays a looping rolling sound per entity. Kept behind a trait so the RollingSoundSystem can be
+/// tested without an audio device.
+pub trait RollingSoundSink: Send + Sync {
+    /// Starts the rolling sound of the entity if it isn't playing yet, and updates its volume
+    /// and pitch.
+    fn play(&mut self, entity: Entity, volume: f32, pitch: f32);
+    /// Stops the rolling sound of the entity, if it is playing.
+    fn stop(&mut self, entity: Entity);
+}
+
+/// A RollingSoundSink that doesn't make a sound.
+pub struct SilentSink;
+
+impl RollingSoundSink for SilentSink {
+    fn play(&mut self, _entity: Entity, _volume: f32, _pitch: f32) {}
+    fn stop(&mut self, _entity: Entity) {}
+}
+
+/// The sink the RollingSoundSystem plays its sounds on. Silent unless another sink is inserted.
+pub struct RollingSounds {
+    pub sink: Box<dyn RollingSoundSink>,
+}
+
+impl Default for RollingSounds {
+    fn default() -> Self {
+        RollingSounds {
+            sink: Box::new(SilentSink),
+        }
+    }
+}
+
+/// The kinds of one-off sound effects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundKind {
+    /// Something entered the world.
+    Spawn,
+}
+
+/// Requests a one-off sound effect at a position in the world. Sent through an EventChannel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoundEvent {
+    pub kind: SoundKind,
+    pub position: Vector3<f32>,
+    /// Between 0.0 and 1.0.
+    pub volume: f32,
+}
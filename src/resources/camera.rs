@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Configures how the main camera zooms along with the topplegrass swarm, and shakes in strong
+/// wind. Loaded from `resources/camera.ron`.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct CameraConfig {
+    /// Whether the camera zooms out when the swarm spreads, and in when it clusters.
+    pub zoom_to_swarm: bool,
+    /// Smallest half height of the view, in world units, however close the swarm clusters.
+    pub min_zoom: f32,
+    /// Largest half height of the view, in world units, however far the swarm spreads.
+    pub max_zoom: f32,
+    /// The view is this many times larger than the bounding radius of the swarm, so the
+    /// outermost topplegrass doesn't end up right at the edge of the screen.
+    pub zoom_padding: f32,
+    /// How quickly the zoom follows changes in the spread of the swarm, per second.
+    pub zoom_rate: f32,
+    /// Whether the camera shakes in strong wind.
+    pub shake_enabled: bool,
+    /// The camera only shakes when the wind is faster than this.
+    pub shake_threshold: f32,
+    /// Distance in world units the camera shakes by, per unit of wind speed above the threshold.
+    pub shake_strength: f32,
+    /// The camera never shakes by more than this distance, however strong the wind.
+    pub shake_max_offset: f32,
+    /// How quickly the shake builds up and dies down when the wind changes, per second.
+    pub shake_rate: f32,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        CameraConfig {
+            zoom_to_swarm: false,
+            min_zoom: 4.0,
+            max_zoom: 20.0,
+            zoom_padding: 1.5,
+            zoom_rate: 1.0,
+            shake_enabled: false,
+            shake_threshold: 4.0,
+            shake_strength: 0.03,
+            shake_max_offset: 0.1,
+            shake_rate: 2.0,
+        }
+    }
+}
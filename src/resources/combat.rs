@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Tunable parameters for combat, loaded from `resources/combat.ron`.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct CombatConfig {
+    /// A predator catches prey within its Size times this multiplier.
+    pub capture_radius_multiplier: f32,
+}
+
+impl Default for CombatConfig {
+    fn default() -> Self {
+        CombatConfig {
+            capture_radius_multiplier: 1.5,
+        }
+    }
+}
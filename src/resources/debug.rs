@@ -1,4 +1,28 @@
-#[derive(Default)]
+use amethyst::ecs::Entity;
+
 pub struct DebugConfig {
     pub visible: bool,
+    /// Whether the BoundsWireframeSystem draws the world bounds. Only drawn while the debug view
+    /// is visible.
+    pub show_bounds: bool,
+    /// Whether the VelocityDebugSystem draws the velocity of every moving entity. Only drawn while
+    /// the debug view is visible.
+    pub show_velocities: bool,
+    /// Length of a drawn velocity line per unit of speed.
+    pub velocity_line_scale: f32,
+}
+
+impl Default for DebugConfig {
+    fn default() -> Self {
+        DebugConfig {
+            visible: false,
+            show_bounds: false,
+            show_velocities: false,
+            velocity_line_scale: 0.5,
+        }
+    }
 }
+
+/// The entity last picked with the cursor by the PickingSystem, if any.
+#[derive(Default)]
+pub struct PickedEntity(pub Option<Entity>);
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::components::creatures::CreatureType;
+
+/// How far outside of the world bounds entities may wander before the OutOfBoundsDespawnSystem
+/// deletes them, loaded from `resources/despawn.ron`. Small debris can be removed right at the
+/// edge, while large creatures may be kept around a bit longer in case they come back.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct DespawnConfig {
+    /// Margin for entities whose species has no margin of its own.
+    pub default_margin: f32,
+    /// Margins per species.
+    pub margins: HashMap<CreatureType, f32>,
+}
+
+impl DespawnConfig {
+    /// Returns the margin for the given species, or the default margin if the species is unknown
+    /// or has no margin configured.
+    pub fn margin(&self, creature_type: Option<&CreatureType>) -> f32 {
+        creature_type
+            .and_then(|creature_type| self.margins.get(creature_type))
+            .cloned()
+            .unwrap_or(self.default_margin)
+    }
+}
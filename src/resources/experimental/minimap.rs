@@ -0,0 +1,9 @@
+use amethyst::core::math::Vector2;
+
+/// Positions of the topplegrass in the world, normalized to [0, 1] x [0, 1] within the world
+/// bounds so a minimap can draw them regardless of its size. Updated every frame by the
+/// MinimapSystem.
+#[derive(Default)]
+pub struct MinimapData {
+    pub topplegrass_positions: Vec<Vector2<f32>>,
+}
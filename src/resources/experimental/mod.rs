@@ -1,2 +1,13 @@
+pub mod despawn;
+pub mod minimap;
+pub mod physics;
+pub mod sandbox_mode;
+pub mod scent;
 pub mod spatial_grid;
+pub mod spawning_enabled;
+pub mod terrain;
+pub mod topplegrass;
+pub mod two_dimensional_mode;
 pub mod wind;
+pub mod wind_field;
+pub mod wind_source;
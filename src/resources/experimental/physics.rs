@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Tunable parameters for the (very simple) physics systems, loaded from `resources/physics.ron`.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct PhysicsConfig {
+    /// The height of the ground plane that falling entities land on.
+    pub ground_height: f32,
+    /// Number of steps into which the GravitySystem divides each frame. More substeps means a
+    /// more accurate fall, and less chance of an entity tunneling through the ground.
+    pub gravity_substeps: u32,
+    /// Falling entities that get within this distance above the ground are snapped onto it,
+    /// so float rounding doesn't leave them hovering just above it.
+    pub ground_tolerance: f32,
+    /// The longest time in seconds a single gravity substep may cover. On slow frames the
+    /// GravitySystem takes more substeps than gravity_substeps to stay below this.
+    pub max_substep_seconds: f32,
+    /// No entity may move faster than this, whatever its own max_movement_speed. A last line of
+    /// defence against forces stacking up into runaway velocities, so it should be set well above
+    /// any speed reached in normal play.
+    pub global_speed_limit: f32,
+    /// Fraction of their vertical speed that falling entities lose per second to air resistance,
+    /// near the ground. Zero disables air resistance.
+    pub air_drag: f32,
+    /// Air resistance at WorldBounds::ceiling, relative to that near the ground.
+    pub ceiling_air_density: f32,
+    /// Shape of the change in air resistance with altitude. 1.0 thins the air evenly all the
+    /// way up; above 1.0 the air stays dense longer and thins out quickly near the ceiling.
+    pub air_density_exponent: f32,
+    /// Height above the ground of the band in which gravity weakens, to cushion landings. Zero
+    /// disables the cushion.
+    pub landing_cushion_height: f32,
+    /// Fraction of the full gravity that is left right at the ground. Gravity ramps down to this
+    /// from the top of the cushion band.
+    pub landing_cushion_gravity: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        PhysicsConfig {
+            ground_height: 0.5,
+            gravity_substeps: 4,
+            ground_tolerance: 0.001,
+            max_substep_seconds: 1.0 / 120.0,
+            global_speed_limit: 100.0,
+            air_drag: 0.0,
+            ceiling_air_density: 0.3,
+            air_density_exponent: 1.0,
+            landing_cushion_height: 0.0,
+            landing_cushion_gravity: 0.3,
+        }
+    }
+}
+
+/// Runtime state of the physics systems that can be changed while the simulation is running.
+pub struct Physics {
+    /// While disabled, the GravitySystem leaves falling entities hanging in the air.
+    /// Toggled with the ToggleGravity action.
+    pub gravity_enabled: bool,
+}
+
+impl Default for Physics {
+    fn default() -> Self {
+        Physics {
+            gravity_enabled: true,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+/// When enabled, the TopplegrassSpawnSystem no longer spawns topplegrass by itself; it only spawns
+/// topplegrass when asked to through the SpawnTopplegrass debug command. Useful for manual testing.
+/// Can be toggled at runtime with the ToggleSandbox action.
+#[derive(Default)]
+pub struct SandboxMode(pub bool);
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+/// Scent below this strength is dropped from the grid, so cells that nobody visits anymore don't
+/// linger forever.
+const MIN_SCENT: f32 = 1e-4;
+
+/// A grid of scent strengths that creatures leave behind as they move. Scent decays
+/// exponentially over time, so recent trails are stronger than old ones.
+pub struct ScentGrid {
+    cell_size: f32,
+    /// Time in seconds for the scent in a cell to decay to half its strength.
+    half_life: f32,
+    cells: HashMap<(i32, i32), f32>,
+}
+
+impl Default for ScentGrid {
+    fn default() -> Self {
+        ScentGrid::new(1.0, 5.0)
+    }
+}
+
+impl ScentGrid {
+    pub fn new(cell_size: f32, half_life: f32) -> Self {
+        ScentGrid {
+            cell_size,
+            half_life,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Adds the given amount of scent to the cell containing the given position.
+    pub fn deposit(&mut self, x: f32, y: f32, amount: f32) {
+        let cell = self.cell(x, y);
+        *self.cells.entry(cell).or_insert(0.0) += amount;
+    }
+
+    /// Returns the strength of the scent in the cell containing the given position.
+    pub fn scent_at(&self, x: f32, y: f32) -> f32 {
+        self.cells.get(&self.cell(x, y)).cloned().unwrap_or(0.0)
+    }
+
+    /// Lets the scent in every cell decay for delta_seconds.
+    pub fn decay(&mut self, delta_seconds: f32) {
+        if self.half_life <= 0.0 {
+            self.cells.clear();
+            return;
+        }
+        let factor = 0.5f32.powf(delta_seconds / self.half_life);
+        self.cells.retain(|_, scent| {
+            *scent *= factor;
+            *scent > MIN_SCENT
+        });
+    }
+
+    fn cell(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deposited_scent_decays_to_near_zero() {
+        let mut grid = ScentGrid::new(1.0, 2.0);
+        grid.deposit(0.5, 0.5, 1.0);
+        assert_eq!(grid.scent_at(0.9, 0.1), 1.0);
+        assert_eq!(grid.scent_at(1.5, 0.5), 0.0);
+
+        grid.decay(2.0);
+        assert!((grid.scent_at(0.5, 0.5) - 0.5).abs() < 1e-5);
+
+        for _ in 0..60 {
+            grid.decay(0.5);
+        }
+        assert!(grid.scent_at(0.5, 0.5) < 1e-3);
+    }
+}
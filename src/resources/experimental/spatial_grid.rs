@@ -1,6 +1,9 @@
 use amethyst::{
-    core::{math::Vector2, transform::Transform},
-    ecs::{BitSet, Entity},
+    core::{
+        math::{Vector2, Vector3},
+        transform::Transform,
+    },
+    ecs::{prelude::WorldExt, BitSet, Entity, Join, World},
 };
 
 use std::collections::HashMap;
@@ -44,8 +47,16 @@ impl SpatialGrid {
     // The range of the query is defined by the range input.
     pub fn query(&self, transform: &Transform, range: f32) -> BitSet {
         let global_matrix = transform.global_matrix();
-        let x_cell = (global_matrix[(0, 3)] / self.cell_size).floor() as i32;
-        let y_cell = (global_matrix[(1, 3)] / self.cell_size).floor() as i32;
+        self.query_position(
+            Vector2::new(global_matrix[(0, 3)], global_matrix[(1, 3)]),
+            range,
+        )
+    }
+
+    // Query the entities close to a position in the xy plane.
+    pub fn query_position(&self, position: Vector2<f32>, range: f32) -> BitSet {
+        let x_cell = (position.x / self.cell_size).floor() as i32;
+        let y_cell = (position.y / self.cell_size).floor() as i32;
         let integer_range = (range / self.cell_size).ceil() as i32;
         let mut entities = BitSet::new();
         for x in -integer_range..(integer_range + 1) {
@@ -62,13 +73,29 @@ impl SpatialGrid {
     }
 }
 
+/// Returns the entities in the SpatialGrid of the world whose Transform lies within radius of
+/// center. Only finds entities that were in the grid when it was last rebuilt by the
+/// SpatialGridSystem, but measures the distance from their current position.
+pub fn entities_in_radius(world: &World, center: Vector3<f32>, radius: f32) -> Vec<Entity> {
+    let grid = world.read_resource::<SpatialGrid>();
+    let nearby_entities = grid.query_position(center.xy(), radius);
+    let entities = world.entities();
+    let transforms = world.read_storage::<Transform>();
+    let squared_radius = radius * radius;
+    (&entities, &transforms, &nearby_entities)
+        .join()
+        .filter(|(_, transform, _)| {
+            let position = transform.global_matrix().column(3).xyz();
+            (position - center).norm_squared() <= squared_radius
+        })
+        .map(|(entity, _, _)| entity)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use amethyst::{
-        core::transform::Transform,
-        ecs::{prelude::WorldExt, Builder, World},
-    };
+    use amethyst::ecs::Builder;
 
     #[test]
     fn grid_creation_insertion_and_query() {
@@ -101,4 +128,33 @@ mod tests {
                 == 3
         );
     }
+
+    #[test]
+    fn entities_in_radius_returns_only_nearby_entities() {
+        let mut world = World::new();
+        world.register::<Transform>();
+        let mut grid = SpatialGrid::new(1.0);
+        let mut spawn_at = |world: &mut World, x: f32, y: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, y, 0.0);
+            transform.copy_local_to_global();
+            let entity = world.create_entity().with(transform.clone()).build();
+            grid.insert(entity, &transform);
+            entity
+        };
+        let center = spawn_at(&mut world, 0.0, 0.0);
+        let near = spawn_at(&mut world, 1.5, 0.5);
+        // In a neighbouring cell of the grid, but too far away.
+        let corner = spawn_at(&mut world, 1.9, 1.9);
+        let far = spawn_at(&mut world, 8.0, 0.0);
+        world.insert(grid);
+
+        let mut found = entities_in_radius(&world, Vector3::new(0.2, 0.0, 0.0), 2.0);
+        found.sort();
+        let mut expected = vec![center, near];
+        expected.sort();
+        assert_eq!(found, expected);
+        assert!(!found.contains(&corner));
+        assert!(!found.contains(&far));
+    }
 }
@@ -0,0 +1,11 @@
+/// When disabled, the TopplegrassSpawnSystem spawns no topplegrass at all, while the existing
+/// topplegrass keeps rolling and despawning, so the world slowly drains. Unlike SandboxMode, this
+/// also ignores the SpawnTopplegrass debug command. Can be toggled at runtime with the
+/// ToggleSpawning action.
+pub struct SpawningEnabled(pub bool);
+
+impl Default for SpawningEnabled {
+    fn default() -> Self {
+        SpawningEnabled(true)
+    }
+}
@@ -0,0 +1,98 @@
+use amethyst::core::math::Vector2;
+use serde::{Deserialize, Serialize};
+
+/// The height of the ground, loaded from `resources/terrain.ron`. The heights are given on a grid
+/// of points cell_size apart, row by row from the origin, and interpolated in between. Outside of
+/// the grid, and without any heights at all, the ground is flat.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct Terrain {
+    /// Position of the first grid point, in the bottom left corner of the grid.
+    pub origin: Vector2<f32>,
+    pub cell_size: f32,
+    /// Number of grid points in every row.
+    pub columns: usize,
+    pub heights: Vec<f32>,
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        Terrain {
+            origin: Vector2::new(0.0, 0.0),
+            cell_size: 1.0,
+            columns: 0,
+            heights: Vec::new(),
+        }
+    }
+}
+
+impl Terrain {
+    /// Returns the rate at which the height of the ground changes along x and along y at the
+    /// given point.
+    pub fn gradient(&self, point: Vector2<f32>) -> Vector2<f32> {
+        let (column, row, fx, fy) = match self.cell(point) {
+            Some(cell) => cell,
+            None => return Vector2::new(0.0, 0.0),
+        };
+        let height = |column: usize, row: usize| self.heights[row * self.columns + column];
+        let h00 = height(column, row);
+        let h10 = height(column + 1, row);
+        let h01 = height(column, row + 1);
+        let h11 = height(column + 1, row + 1);
+        Vector2::new(
+            ((h10 - h00) * (1.0 - fy) + (h11 - h01) * fy) / self.cell_size,
+            ((h01 - h00) * (1.0 - fx) + (h11 - h10) * fx) / self.cell_size,
+        )
+    }
+
+    /// Returns how steep the ground is at the given point, as the rise per unit of horizontal
+    /// distance in the steepest direction.
+    pub fn slope(&self, point: Vector2<f32>) -> f32 {
+        self.gradient(point).magnitude()
+    }
+
+    /// Returns the grid cell containing the point, as the column and row of its bottom left grid
+    /// point and the fractions of the way across the cell along x and y.
+    fn cell(&self, point: Vector2<f32>) -> Option<(usize, usize, f32, f32)> {
+        if self.columns < 2 || self.cell_size <= 0.0 {
+            return None;
+        }
+        let rows = self.heights.len() / self.columns;
+        if rows < 2 {
+            return None;
+        }
+        let grid = (point - self.origin) / self.cell_size;
+        let max_column = (self.columns - 1) as f32;
+        let max_row = (rows - 1) as f32;
+        if grid.x < 0.0 || grid.y < 0.0 || grid.x > max_column || grid.y > max_row {
+            return None;
+        }
+        // Points on the far edges belong to the last cell rather than to one beyond the grid.
+        let column = grid.x.floor().min(max_column - 1.0);
+        let row = grid.y.floor().min(max_row - 1.0);
+        Some((column as usize, row as usize, grid.x - column, grid.y - row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slope_follows_the_grid_and_is_flat_outside_of_it() {
+        // A ramp rising by 2 per unit along x, next to a flat plateau.
+        let terrain = Terrain {
+            origin: Vector2::new(0.0, 0.0),
+            cell_size: 1.0,
+            columns: 3,
+            heights: vec![0.0, 2.0, 2.0, 0.0, 2.0, 2.0],
+        };
+        let gradient = terrain.gradient(Vector2::new(0.5, 0.5));
+        assert!((gradient.x - 2.0).abs() < 1e-6);
+        assert!(gradient.y.abs() < 1e-6);
+        assert!(terrain.slope(Vector2::new(1.5, 0.5)).abs() < 1e-6);
+        assert!(terrain.slope(Vector2::new(-1.0, 0.5)).abs() < 1e-6);
+        assert!(Terrain::default().slope(Vector2::new(0.5, 0.5)).abs() < 1e-6);
+    }
+}
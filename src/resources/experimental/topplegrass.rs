@@ -0,0 +1,279 @@
+use amethyst::core::math::Vector2;
+use serde::{Deserialize, Serialize};
+
+/// Tunable parameters for the topplegrass systems, loaded from `resources/topplegrass.ron`.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct TopplegrassConfig {
+    /// Multiplier applied to the rolling rotation, either 1.0 or -1.0.
+    /// Whether the mesh appears to roll forwards or backwards depends on how it was modelled.
+    /// To find the right sign: set a wind blowing along the positive x axis and watch a
+    /// topplegrass roll. If the top of the mesh moves in the direction of the wind, the sign is
+    /// correct; if it moves against the wind, flip it.
+    pub roll_direction: f32,
+    /// Around which axes rolling topplegrass rotates, unless its variant says otherwise.
+    pub rotation_axes: RotationAxes,
+    /// When a topplegrass jumps, its vertical velocity is its horizontal speed multiplied by a
+    /// random factor between min_jump_factor and max_jump_factor.
+    pub min_jump_factor: f32,
+    pub max_jump_factor: f32,
+    /// The vertical velocity of a jump is clamped between min_jump_impulse and max_jump_impulse,
+    /// so slow topplegrass still hops a little and fast topplegrass doesn't launch into orbit.
+    pub min_jump_impulse: f32,
+    pub max_jump_impulse: f32,
+    /// If enabled, the vertical velocity of a jump is picked at random between min_jump_impulse
+    /// and max_jump_impulse, regardless of the horizontal speed of the topplegrass.
+    pub absolute_jump_impulse: bool,
+    /// On takeoff, the horizontal velocity of the topplegrass is increased by this fraction, up
+    /// to its max_movement_speed. Only noticeable on topplegrass dragged along by the wind
+    /// through a DragProfile, as other topplegrass always move exactly with the wind.
+    pub jump_forward_boost: f32,
+    /// Number of topplegrass to spawn at random positions as soon as the simulation starts.
+    pub initial_population: u32,
+    /// Whether fast rolling topplegrass kicks up debris behind it.
+    pub debris_enabled: bool,
+    /// Topplegrass only kicks up debris when moving faster than this speed.
+    pub debris_speed_threshold: f32,
+    /// Pieces of debris emitted per second, per unit of speed.
+    pub debris_rate: f32,
+    /// Pieces of debris blown into the world per second, per unit of wind speed. Zero disables it.
+    pub ambient_debris_rate: f32,
+    /// Topplegrass further than this distance from the camera is not rendered.
+    pub cull_distance: f32,
+    /// Maximum sideways speed with which rolling topplegrass wobbles. Zero disables wobbling.
+    pub wobble_amplitude: f32,
+    /// Number of full wobbles per second.
+    pub wobble_frequency: f32,
+    /// Colliding topplegrass merge into one bigger topplegrass if their speeds differ by less
+    /// than this value.
+    pub merge_speed_threshold: f32,
+    /// Topplegrass never merge into one bigger than this many times the standard scale.
+    pub max_merge_scale: f32,
+    /// If true, topplegrass spawns on both borders the wind is blowing away from, weighted by
+    /// how closely the wind blows towards each, instead of only on the nearest one.
+    pub blend_spawn_edges: bool,
+    /// How sharply blended spawns favour the border the wind is most aligned with. 1.0 weights
+    /// each border linearly by the wind's alignment with it, higher values favour the dominant
+    /// border more. Has no effect unless blend_spawn_edges is set.
+    pub spawn_edge_weight_exponent: f32,
+    /// If set, topplegrass spawns on the left, bottom, right and top border with a chance
+    /// proportional to these weights, whichever way the wind blows. Overrides all the other ways
+    /// of picking the spawn border.
+    pub spawn_edge_weights: Option<[f32; 4]>,
+    /// If true, consecutive topplegrass spawn evenly spread out along the upwind border, instead
+    /// of at random points on it. Has no effect if blend_spawn_edges is set.
+    pub stratified_spawns: bool,
+    /// If more than zero, the upwind border is divided into this many segments, and topplegrass
+    /// avoids spawning in a segment that was used less than spawn_segment_cooldown seconds ago.
+    /// Has no effect if blend_spawn_edges or stratified_spawns is set.
+    pub spawn_segments: usize,
+    pub spawn_segment_cooldown: f32,
+    /// Topplegrass moving slower than this horizontal speed is considered idle...
+    pub idle_speed_threshold: f32,
+    /// ...and is despawned after having been idle for this many seconds in a row.
+    pub idle_timeout: f32,
+    /// Maximum rotation speed of a rolling topplegrass around each axis, in radians per second.
+    /// Keeps very fast topplegrass from spinning so fast that it appears to strobe.
+    pub max_angular_speed: f32,
+    /// Wind speed at which topplegrass jumps as often and as high as it can. In calmer wind it
+    /// jumps less often and less high, without wind it doesn't jump at all.
+    /// Zero or less makes jumping independent of the wind.
+    pub hop_full_wind_speed: f32,
+    /// The chance to jump grows by this fraction for every second a topplegrass has been on the
+    /// ground since it last landed...
+    pub hop_ramp_rate: f32,
+    /// ...up to this many times the normal chance.
+    pub hop_ramp_max: f32,
+    /// Fraction of the wind velocity that freshly spawned topplegrass starts out with. Below 1.0,
+    /// the wind gradually accelerates it up to speed.
+    pub spawn_velocity_fraction: f32,
+    /// If true, topplegrass that lands slower than freeze_speed_threshold comes to rest and stops
+    /// rolling, until the wind at its position exceeds unfreeze_wind_speed.
+    pub freeze_on_land: bool,
+    pub freeze_speed_threshold: f32,
+    pub unfreeze_wind_speed: f32,
+    /// Fraction of its horizontal velocity that topplegrass on the ground loses per second, so it
+    /// comes to a halt when the wind dies down instead of rolling on forever.
+    pub ground_friction: f32,
+    /// Topplegrass in the shadow behind an obstacle is sheltered from the wind. Right behind the
+    /// obstacle the wind is weakened by this fraction, at the end of the shadow it is back at
+    /// full strength. Zero disables wind shadows.
+    pub wind_shadow_strength: f32,
+    /// Length of the shadow in obstacle radii, in a wind of wind_shadow_reference_speed. The
+    /// shadow grows and shrinks in proportion to the wind speed.
+    pub wind_shadow_length: f32,
+    pub wind_shadow_reference_speed: f32,
+    /// How much wider the shadow gets for every unit further away from the obstacle.
+    pub wind_shadow_spread: f32,
+    /// Topplegrass rolling over the ground faster than this makes a rolling sound...
+    pub rolling_sound_min_speed: f32,
+    /// ...which gets louder and higher pitched up to this speed.
+    pub rolling_sound_full_speed: f32,
+    /// Volume of the sound cue played when topplegrass spawns. Zero disables it.
+    pub spawn_sound_volume: f32,
+    /// Least time in seconds between two spawn sound cues, so a burst of spawns doesn't play a
+    /// pile of sounds at once.
+    pub spawn_sound_interval: f32,
+    /// The kinds of topplegrass that can spawn, each picked with a chance proportional to its
+    /// weight. If empty, all topplegrass is of the standard size.
+    pub variants: Vec<TopplegrassVariant>,
+    /// Fraction of the velocity of a creature that is passed on to topplegrass it runs into.
+    pub knock_coefficient: f32,
+    /// Pinned topplegrass stays in place, but rolls as if it moved at this velocity.
+    pub pinned_roll_velocity: Vector2<f32>,
+    /// Areas where no topplegrass spawns.
+    pub exclusion_zones: Vec<ExclusionZone>,
+    /// How many more spawn locations are tried when one falls inside an exclusion zone or on too
+    /// steep terrain, before falling back to the middle of a world border or the world center.
+    pub exclusion_max_retries: usize,
+    /// Steepest slope of the terrain that topplegrass spawns on, as the rise per unit of
+    /// horizontal distance.
+    pub max_spawn_slope: f32,
+    /// Seeds dropped per second by every topplegrass, on average. Zero disables seed drops.
+    pub seed_drop_rate: f32,
+    /// Seconds after being dropped until a seed germinates...
+    pub germination_delay: f32,
+    /// ...and the chance that it then grows into a plant.
+    pub germination_chance: f32,
+}
+
+impl Default for TopplegrassConfig {
+    fn default() -> Self {
+        TopplegrassConfig {
+            roll_direction: 1.0,
+            rotation_axes: RotationAxes::default(),
+            min_jump_factor: 0.2,
+            max_jump_factor: 0.35,
+            min_jump_impulse: 0.3,
+            max_jump_impulse: 1.2,
+            absolute_jump_impulse: false,
+            jump_forward_boost: 0.0,
+            initial_population: 0,
+            debris_enabled: false,
+            debris_speed_threshold: 3.0,
+            debris_rate: 1.0,
+            ambient_debris_rate: 0.0,
+            cull_distance: 40.0,
+            wobble_amplitude: 0.0,
+            wobble_frequency: 0.5,
+            merge_speed_threshold: 0.5,
+            max_merge_scale: 3.0,
+            blend_spawn_edges: false,
+            spawn_edge_weight_exponent: 1.0,
+            spawn_edge_weights: None,
+            stratified_spawns: false,
+            spawn_segments: 0,
+            spawn_segment_cooldown: 30.0,
+            idle_speed_threshold: 0.1,
+            idle_timeout: 20.0,
+            max_angular_speed: 15.0,
+            hop_full_wind_speed: 3.0,
+            hop_ramp_rate: 0.5,
+            hop_ramp_max: 3.0,
+            spawn_velocity_fraction: 1.0,
+            freeze_on_land: false,
+            freeze_speed_threshold: 0.3,
+            unfreeze_wind_speed: 3.0,
+            ground_friction: 0.0,
+            wind_shadow_strength: 0.7,
+            wind_shadow_length: 5.0,
+            wind_shadow_reference_speed: 3.0,
+            wind_shadow_spread: 0.2,
+            rolling_sound_min_speed: 0.5,
+            rolling_sound_full_speed: 5.0,
+            spawn_sound_volume: 0.3,
+            spawn_sound_interval: 0.5,
+            variants: Vec::new(),
+            knock_coefficient: 0.5,
+            pinned_roll_velocity: Vector2::new(1.0, 0.0),
+            exclusion_zones: Vec::new(),
+            exclusion_max_retries: 10,
+            max_spawn_slope: 1.0,
+            seed_drop_rate: 0.0,
+            germination_delay: 20.0,
+            germination_chance: 0.5,
+        }
+    }
+}
+
+/// A circular area on the ground that is kept clear of spawning topplegrass, for example around
+/// a nest.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExclusionZone {
+    pub center: Vector2<f32>,
+    pub radius: f32,
+}
+
+impl ExclusionZone {
+    /// Whether the given point on the ground lies within the zone.
+    pub fn contains(&self, point: Vector2<f32>) -> bool {
+        (point - self.center).magnitude_squared() < self.radius * self.radius
+    }
+}
+
+/// A kind of topplegrass, such as a small or a large one.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TopplegrassVariant {
+    pub name: String,
+    /// Relative chance that a spawning topplegrass is of this variant.
+    pub weight: f32,
+    /// Size of this variant, as a multiple of the standard topplegrass scale.
+    pub scale: f32,
+    /// Multiplier for how strongly the wind drags this variant along, on top of the effect of
+    /// its size. Below 1.0 for heavy variants, above 1.0 for light ones.
+    pub wind_susceptibility: f32,
+    /// Multiplier for how fast this variant rotates when rolling at a given speed.
+    pub roll_speed: f32,
+    /// Around which axes this variant rotates. Uses TopplegrassConfig::rotation_axes if not set.
+    #[serde(default)]
+    pub rotation_axes: Option<RotationAxes>,
+}
+
+/// Which rotations make up the rolling animation of a topplegrass.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct RotationAxes {
+    /// Whether the topplegrass rolls around the x axis when moving along the y axis.
+    pub x: bool,
+    /// Whether the topplegrass rolls around the y axis when moving along the x axis.
+    pub y: bool,
+    /// Greatest angular speed in radians per second of a slow back and forth twist around the
+    /// vertical axis, at the pace of TopplegrassConfig::wobble_frequency. Zero disables it.
+    pub z_wobble: f32,
+}
+
+impl Default for RotationAxes {
+    fn default() -> Self {
+        RotationAxes {
+            x: true,
+            y: true,
+            z_wobble: 0.0,
+        }
+    }
+}
+
+impl TopplegrassVariant {
+    /// Picks the index of a variant at random, with chances proportional to their weights. The
+    /// roll, between 0.0 and 1.0, decides which one. Returns None if there are no variants or
+    /// none of them has a positive weight.
+    pub fn choose(variants: &[TopplegrassVariant], roll: f32) -> Option<usize> {
+        let total: f32 = variants.iter().map(|variant| variant.weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut remaining = roll * total;
+        for (index, variant) in variants.iter().enumerate() {
+            let weight = variant.weight.max(0.0);
+            if remaining < weight {
+                return Some(index);
+            }
+            remaining -= weight;
+        }
+        // Rounding errors can leave a sliver at the end, it belongs to the last variant that
+        // can be picked.
+        variants.iter().rposition(|variant| variant.weight > 0.0)
+    }
+}
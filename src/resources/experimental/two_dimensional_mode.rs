@@ -0,0 +1,4 @@
+/// When enabled, all motion is locked to the ground plane: topplegrass never hops up into the
+/// air and gravity is not integrated. Useful for a top-down, analytical view of the simulation.
+#[derive(Default)]
+pub struct TwoDimensionalMode(pub bool);
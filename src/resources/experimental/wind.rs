@@ -1,21 +1,90 @@
 use amethyst::core::math::Vector2;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Number of recent wind vectors averaged by Wind::smoothed() if not configured otherwise.
+const DEFAULT_SMOOTHING_SAMPLES: usize = 10;
+/// Highest wind speed the WindMagnitudeControllerSystem allows, if not configured otherwise.
+const DEFAULT_MAX_SPEED: f32 = 5.0;
+/// Largest change in wind speed per second the WindMagnitudeControllerSystem allows, if not
+/// configured otherwise.
+const DEFAULT_MAX_SPEED_CHANGE: f32 = 2.0;
 
 /// Keeps track of the wind conditions in the world.
-/// Currently, wind is represented by a 2D vector.
-#[derive(Deserialize, Serialize)]
+/// Currently, wind is represented by a 2D vector. Optional layers can make the wind blow
+/// differently at certain heights; anywhere outside of the layers, the wind vector applies.
+/// Also keeps a short history of recent wind vectors, so systems that should not jitter along
+/// with sudden changes in the wind can use the smoothed wind instead.
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 #[serde(deny_unknown_fields)]
 pub struct Wind {
     pub wind: Vector2<f32>,
+    /// Number of recent wind vectors averaged by smoothed().
+    pub smoothing_samples: usize,
+    /// The wind speed never exceeds this value, however the contributions to it add up.
+    pub max_speed: f32,
+    /// The wind speed never changes by more than this value per second.
+    pub max_speed_change: f32,
+    /// How many times per second the WindMagnitudeControllerSystem recomputes the target wind
+    /// speed. Zero or less recomputes it every frame.
+    pub update_hz: f32,
+    /// Height ranges in which the wind blows differently, for example a jet stream high up.
+    pub layers: Vec<WindLayer>,
+    #[serde(skip)]
+    history: VecDeque<Vector2<f32>>,
 }
 
 impl Wind {
     pub fn new(x: f32, y: f32) -> Wind {
         Wind {
             wind: Vector2::new(x, y),
+            smoothing_samples: DEFAULT_SMOOTHING_SAMPLES,
+            max_speed: DEFAULT_MAX_SPEED,
+            max_speed_change: DEFAULT_MAX_SPEED_CHANGE,
+            update_hz: 0.0,
+            layers: Vec::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Adds the current wind vector to the history, dropping the oldest samples if the history
+    /// grows beyond smoothing_samples.
+    pub fn record_sample(&mut self) {
+        self.history.push_back(self.wind);
+        while self.history.len() > self.smoothing_samples.max(1) {
+            self.history.pop_front();
         }
     }
+
+    /// The average of the recently recorded wind vectors, or the current wind if no samples have
+    /// been recorded yet.
+    pub fn smoothed(&self) -> Vector2<f32> {
+        if self.history.is_empty() {
+            return self.wind;
+        }
+        let sum = self
+            .history
+            .iter()
+            .fold(Vector2::zeros(), |sum, sample| sum + sample);
+        sum / self.history.len() as f32
+    }
+}
+
+impl Wind {
+    /// The wind at the given height: that of the first layer containing the height, or the wind
+    /// vector if there is none.
+    pub fn at_height(&self, z: f32) -> Vector2<f32> {
+        self.layers
+            .iter()
+            .find(|layer| layer.contains(z))
+            .map_or(self.wind, |layer| layer.vector)
+    }
+
+    /// The cardinal direction the wind is blowing towards most closely.
+    pub fn dominant_cardinal(&self) -> Cardinal {
+        Cardinal::nearest(self.wind)
+    }
 }
 
 impl Default for Wind {
@@ -23,3 +92,114 @@ impl Default for Wind {
         Wind::new(2.0, 0.0)
     }
 }
+
+/// A range of heights in which the wind blows with its own vector.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WindLayer {
+    /// Lowest height of the layer, inclusive.
+    pub min_z: f32,
+    /// Highest height of the layer, exclusive.
+    pub max_z: f32,
+    pub vector: Vector2<f32>,
+}
+
+impl WindLayer {
+    pub fn contains(&self, z: f32) -> bool {
+        z >= self.min_z && z < self.max_z
+    }
+}
+
+/// The four main compass directions, with north pointing along the positive y axis and east along
+/// the positive x axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cardinal {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Cardinal {
+    /// Returns the cardinal direction with the largest dot product with the given vector.
+    /// Exactly diagonal vectors are resolved to east or west.
+    pub fn nearest(direction: Vector2<f32>) -> Cardinal {
+        if direction.x.abs() >= direction.y.abs() {
+            if direction.x >= 0.0 {
+                Cardinal::East
+            } else {
+                Cardinal::West
+            }
+        } else if direction.y > 0.0 {
+            Cardinal::North
+        } else {
+            Cardinal::South
+        }
+    }
+}
+
+/// The contributions of the different systems that want to change the wind speed. Systems never
+/// change the wind speed directly; instead they write their contribution here, and the
+/// WindMagnitudeControllerSystem combines them into the actual wind speed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WindSpeedContributions {
+    /// The wind speed without any temporary effects, as set through the debug controls.
+    pub base: f32,
+    /// A new direction for the wind to blow in, as a unit vector. The
+    /// WindMagnitudeControllerSystem turns the wind to it on its next run and clears it again.
+    /// Unlike the direction of the wind vector itself, this is kept while the wind is calm.
+    pub direction: Option<Vector2<f32>>,
+}
+
+impl WindSpeedContributions {
+    pub fn total(&self) -> f32 {
+        self.base
+    }
+}
+
+/// Whether changes made to the wind through the debug controls are logged.
+/// Can be toggled at runtime with the ToggleWindLogging action.
+/// Enabled by default in debug builds, disabled in release builds.
+pub struct WindLoggingEnabled(pub bool);
+
+impl Default for WindLoggingEnabled {
+    fn default() -> Self {
+        WindLoggingEnabled(cfg!(debug_assertions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_direction_maps_to_the_nearest_cardinal() {
+        let expectations = [
+            ((1.0, 0.0), Cardinal::East),
+            ((1.0, 1.0), Cardinal::East),
+            ((0.0, 1.0), Cardinal::North),
+            ((-1.0, 1.0), Cardinal::West),
+            ((-1.0, 0.0), Cardinal::West),
+            ((-1.0, -1.0), Cardinal::West),
+            ((0.0, -1.0), Cardinal::South),
+            ((1.0, -1.0), Cardinal::East),
+        ];
+        for ((x, y), cardinal) in expectations.iter() {
+            assert_eq!(Wind::new(*x, *y).dominant_cardinal(), *cardinal);
+        }
+        assert_eq!(Wind::new(0.3, 2.0).dominant_cardinal(), Cardinal::North);
+        assert_eq!(Wind::new(-2.0, -2.5).dominant_cardinal(), Cardinal::South);
+    }
+
+    #[test]
+    fn high_entities_catch_the_upper_layer() {
+        let mut wind = Wind::new(2.0, 0.0);
+        wind.layers.push(WindLayer {
+            min_z: 3.0,
+            max_z: 100.0,
+            vector: Vector2::new(0.0, 6.0),
+        });
+        assert_eq!(wind.at_height(0.5), Vector2::new(2.0, 0.0));
+        assert_eq!(wind.at_height(10.0), Vector2::new(0.0, 6.0));
+        assert_eq!(wind.at_height(100.0), Vector2::new(2.0, 0.0));
+    }
+}
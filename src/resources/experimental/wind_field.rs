@@ -0,0 +1,173 @@
+use amethyst::core::math::Vector2;
+use serde::{Deserialize, Serialize};
+
+/// Largest angle in radians by which the turbulence turns the wind, if not configured otherwise.
+const DEFAULT_TURBULENCE: f32 = 0.0;
+/// Distance over which the turbulence changes, if not configured otherwise. Long enough for a
+/// smooth, large scale flow across the default world bounds.
+const DEFAULT_CORRELATION_LENGTH: f32 = 20.0;
+
+/// A circular area in which the wind blows differently than elsewhere, for example a valley or a
+/// gap between mountains. Its wind is added to the global wind.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WindZone {
+    pub center: Vector2<f32>,
+    pub radius: f32,
+    pub wind: Vector2<f32>,
+}
+
+impl WindZone {
+    pub fn contains(&self, position: Vector2<f32>) -> bool {
+        (position - self.center).magnitude() <= self.radius
+    }
+}
+
+/// The local variations on the global wind. Systems that need to know the wind at a specific
+/// position should sample the WindField rather than reading the Wind resource directly.
+/// Loaded from `resources/wind_field.ron`.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct WindField {
+    pub zones: Vec<WindZone>,
+    /// Largest angle in radians by which the wind direction is turned at any position, following
+    /// a smooth noise pattern. Zero disables the turbulence.
+    pub turbulence: f32,
+    /// Distance over which the turbulence changes direction. Short lengths make the wind patchy,
+    /// so nearby topplegrass takes diverging paths; long lengths make it flow smoothly.
+    pub correlation_length: f32,
+}
+
+impl Default for WindField {
+    fn default() -> Self {
+        WindField {
+            zones: Vec::new(),
+            turbulence: DEFAULT_TURBULENCE,
+            correlation_length: DEFAULT_CORRELATION_LENGTH,
+        }
+    }
+}
+
+impl WindField {
+    /// Returns the wind at the given position: the global wind plus the wind of every zone that
+    /// contains the position, turned by the turbulence at that position.
+    pub fn sample(&self, position: Vector2<f32>, global_wind: Vector2<f32>) -> Vector2<f32> {
+        let wind = self
+            .zones
+            .iter()
+            .filter(|zone| zone.contains(position))
+            .fold(global_wind, |wind, zone| wind + zone.wind);
+        if self.turbulence == 0.0 {
+            return wind;
+        }
+        let scaled_position = position / self.correlation_length.max(std::f32::EPSILON);
+        let (sin, cos) = (self.turbulence * value_noise(scaled_position)).sin_cos();
+        Vector2::new(wind.x * cos - wind.y * sin, wind.x * sin + wind.y * cos)
+    }
+}
+
+/// Smooth 2D noise between -1.0 and 1.0, which changes over a distance of about 1.0. Interpolates
+/// between pseudo random values at the integer grid points.
+fn value_noise(position: Vector2<f32>) -> f32 {
+    let (cell_x, cell_y) = (position.x.floor(), position.y.floor());
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (t_x, t_y) = (smooth(position.x - cell_x), smooth(position.y - cell_y));
+    let (x, y) = (cell_x as i32, cell_y as i32);
+    let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+    lerp(
+        lerp(lattice_value(x, y), lattice_value(x + 1, y), t_x),
+        lerp(lattice_value(x, y + 1), lattice_value(x + 1, y + 1), t_x),
+        t_y,
+    )
+}
+
+/// Returns a pseudo random value between -1.0 and 1.0 for the given grid point.
+fn lattice_value(x: i32, y: i32) -> f32 {
+    let mut hash = (x as u32).wrapping_mul(0x27d4_eb2d) ^ (y as u32).wrapping_mul(0x1656_67b1);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash as f32 / std::u32::MAX as f32 * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field() -> WindField {
+        WindField {
+            zones: vec![
+                WindZone {
+                    center: Vector2::new(0.0, 0.0),
+                    radius: 2.0,
+                    wind: Vector2::new(1.0, 0.0),
+                },
+                WindZone {
+                    center: Vector2::new(3.0, 0.0),
+                    radius: 2.0,
+                    wind: Vector2::new(0.0, -1.0),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn outside_all_zones_the_wind_is_global() {
+        let global_wind = Vector2::new(2.0, 0.5);
+        assert_eq!(
+            field().sample(Vector2::new(10.0, 10.0), global_wind),
+            global_wind
+        );
+    }
+
+    #[test]
+    fn inside_a_zone_its_wind_is_added() {
+        let wind = field().sample(Vector2::new(-1.0, 0.0), Vector2::new(2.0, 0.5));
+        assert_eq!(wind, Vector2::new(3.0, 0.5));
+    }
+
+    #[test]
+    fn overlapping_zones_all_contribute() {
+        let wind = field().sample(Vector2::new(1.5, 0.0), Vector2::new(2.0, 0.5));
+        assert_eq!(wind, Vector2::new(3.0, -0.5));
+    }
+
+    #[test]
+    fn shorter_correlation_length_makes_nearby_samples_differ_more() {
+        let variance = |correlation_length: f32| {
+            let field = WindField {
+                turbulence: 1.0,
+                correlation_length,
+                ..Default::default()
+            };
+            let global_wind = Vector2::new(2.0, 0.0);
+            let mut total = 0.0;
+            let mut count = 0;
+            for i in 0..20 {
+                for j in 0..20 {
+                    let position = Vector2::new(i as f32 * 1.7 - 15.0, j as f32 * 1.3 - 12.0);
+                    let neighbour = position + Vector2::new(0.5, 0.0);
+                    let difference =
+                        field.sample(position, global_wind) - field.sample(neighbour, global_wind);
+                    total += difference.magnitude_squared();
+                    count += 1;
+                }
+            }
+            total / count as f32
+        };
+        assert!(variance(1.0) > 4.0 * variance(20.0));
+    }
+
+    #[test]
+    fn turbulence_only_changes_the_wind_direction() {
+        let field = WindField {
+            turbulence: 1.0,
+            correlation_length: 2.0,
+            ..Default::default()
+        };
+        let wind = field.sample(Vector2::new(3.3, -1.7), Vector2::new(2.0, 0.5));
+        assert!((wind.magnitude() - Vector2::new(2.0f32, 0.5).magnitude()).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Where the WindSourceSystem reads wind samples from, loaded from `resources/wind_source.ron`.
+/// Without a file or address, the wind is left to the other wind systems.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct WindSourceConfig {
+    /// File to read the latest wind sample from whenever it changes.
+    pub file: Option<String>,
+    /// Local address to receive wind samples on as UDP datagrams. Requires the
+    /// `wind-source-udp` feature.
+    pub udp_address: Option<String>,
+    /// Time in seconds between two polls of the source.
+    pub poll_interval: f32,
+}
+
+impl Default for WindSourceConfig {
+    fn default() -> Self {
+        WindSourceConfig {
+            file: None,
+            udp_address: None,
+            poll_interval: 1.0,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use rand::{rngs::StdRng, Error, RngCore, SeedableRng};
+
+/// The random number generator shared by the simulation systems. Seeding it makes a run of the
+/// simulation reproducible, which tests rely on. By default it is seeded from system entropy.
+pub struct GameRng(StdRng);
+
+impl GameRng {
+    pub fn seeded(seed: u64) -> GameRng {
+        GameRng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng(StdRng::from_entropy())
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// The random number generator for purely visual effects, like the camera shake. It is kept apart
+/// from the GameRng so that effects, which only run when rendering, don't change the course of a
+/// seeded simulation. It can be seeded the same way.
+#[derive(Default)]
+pub struct EffectsRng(pub GameRng);
+
+impl EffectsRng {
+    pub fn seeded(seed: u64) -> EffectsRng {
+        EffectsRng(GameRng::seeded(seed))
+    }
+}
@@ -1,7 +1,17 @@
 pub mod audio;
+pub mod camera;
+pub mod combat;
 pub mod debug;
+pub mod game_rng;
 pub mod prefabs;
+pub mod replay;
+pub mod spawn_budget;
+pub mod spawn_stats;
+pub mod species_color;
+pub mod stats_export;
+pub mod timeline;
 pub mod world_bounds;
+pub mod world_init;
 
 mod experimental;
 pub use experimental::*;
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::debug_input::DebugCommand;
+
+/// Whether debug commands are recorded, played back, or neither. Recording and playback are
+/// mutually exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReplayMode {
+    Off,
+    Record,
+    Playback,
+}
+
+/// Configures the recording and playback of debug commands, loaded from `resources/replay.ron`.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayConfig {
+    pub mode: ReplayMode,
+    /// File that commands are recorded to, or played back from.
+    pub recording_path: String,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        ReplayConfig {
+            mode: ReplayMode::Off,
+            recording_path: "recording.ron".to_string(),
+        }
+    }
+}
+
+/// A debug command together with the simulated time, in seconds, at which it was issued.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RecordedCommand {
+    pub time: f64,
+    pub command: DebugCommand,
+}
+
+/// All debug commands issued during a session, in the order in which they were issued.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CommandRecording {
+    pub commands: Vec<RecordedCommand>,
+}
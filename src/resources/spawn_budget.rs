@@ -0,0 +1,15 @@
+/// Limits how many CreatureSpawnEvents the CreatureSpawnerSystem handles per frame. Events beyond
+/// the budget are deferred to the next frames, so a burst of spawns doesn't attach all their
+/// prefabs in a single frame. Unlimited by default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpawnBudget {
+    pub max_per_frame: usize,
+}
+
+impl Default for SpawnBudget {
+    fn default() -> Self {
+        SpawnBudget {
+            max_per_frame: std::usize::MAX,
+        }
+    }
+}
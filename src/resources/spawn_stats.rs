@@ -0,0 +1,38 @@
+/// Keeps count of the entities that were spawned and despawned during the simulation. Useful to
+/// detect entities that are never cleaned up, and to tune spawn rates.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpawnStats {
+    pub total_spawned: u64,
+    pub total_despawned: u64,
+    pub currently_alive: u64,
+}
+
+impl SpawnStats {
+    pub fn record_spawn(&mut self) {
+        self.total_spawned += 1;
+        self.currently_alive += 1;
+    }
+
+    pub fn record_despawn(&mut self) {
+        self.total_despawned += 1;
+        self.currently_alive = self.currently_alive.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawning_and_despawning_updates_counters() {
+        let mut stats = SpawnStats::default();
+        stats.record_spawn();
+        stats.record_spawn();
+        stats.record_spawn();
+        stats.record_despawn();
+
+        assert_eq!(stats.total_spawned, 3);
+        assert_eq!(stats.total_despawned, 1);
+        assert_eq!(stats.currently_alive, 2);
+    }
+}
@@ -0,0 +1,23 @@
+use amethyst::renderer::palette::Srgba;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::components::creatures::CreatureType;
+
+/// The color every species is tinted with, so they can be told apart at a glance.
+/// Loaded from `resources/species_colors.ron`. Colors are given as [red, green, blue, alpha].
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SpeciesColor {
+    pub colors: HashMap<CreatureType, [f32; 4]>,
+}
+
+impl SpeciesColor {
+    /// Returns the color of the given species, if one is configured.
+    pub fn color(&self, creature_type: &str) -> Option<Srgba> {
+        self.colors
+            .get(creature_type)
+            .map(|[r, g, b, a]| Srgba::new(*r, *g, *b, *a))
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Configures the periodic export of per-creature stats to a CSV file, for analysing simulation
+/// runs offline. Loaded from `resources/stats_export.ron`.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct StatsExportConfig {
+    pub enabled: bool,
+    /// File the stats are written to. It is overwritten when the simulation starts.
+    pub output_path: String,
+    /// Time in seconds between two samples.
+    pub interval: f32,
+}
+
+impl Default for StatsExportConfig {
+    fn default() -> Self {
+        StatsExportConfig {
+            enabled: false,
+            output_path: "creature_stats.csv".to_string(),
+            interval: 1.0,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Something that happens at a scheduled moment in a scenario.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum TimelineEvent {
+    /// Spawns a creature of the given type at the given position.
+    SpawnCreature {
+        creature_type: String,
+        x: f32,
+        y: f32,
+    },
+    /// Sets the wind to the given vector.
+    SetWind { x: f32, y: f32 },
+}
+
+/// A TimelineEvent scheduled at a simulated time, in seconds since the start of the scenario.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TimelineEntry {
+    pub time: f32,
+    pub event: TimelineEvent,
+    /// Whether the event has been executed already. Every entry fires only once.
+    #[serde(skip)]
+    pub fired: bool,
+}
+
+impl TimelineEntry {
+    pub fn new(time: f32, event: TimelineEvent) -> TimelineEntry {
+        TimelineEntry {
+            time,
+            event,
+            fired: false,
+        }
+    }
+}
+
+/// A scripted scenario: a list of events executed by the TimelineSystem once the simulation
+/// reaches their time. Loaded from `resources/timeline.ron`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct Timeline {
+    pub entries: Vec<TimelineEntry>,
+}
@@ -1,9 +1,14 @@
-#[derive(Default)]
+/// Height of the top of the world, if not set otherwise.
+const DEFAULT_CEILING: f32 = 10.0;
+
+#[derive(Clone)]
 pub struct WorldBounds {
     pub left: f32,
     pub right: f32,
     pub bottom: f32,
     pub top: f32,
+    /// The height at which the air is at its thinnest.
+    pub ceiling: f32,
 }
 
 impl WorldBounds {
@@ -13,6 +18,13 @@ impl WorldBounds {
             right,
             bottom,
             top,
+            ceiling: DEFAULT_CEILING,
         }
     }
 }
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        WorldBounds::new(0.0, 0.0, 0.0, 0.0)
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// A group of creatures of the same type placed in the world when the simulation starts.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldInitEntry {
+    pub creature_type: String,
+    pub x: f32,
+    pub y: f32,
+    pub count: usize,
+    /// The creatures are spread evenly over a circle of this radius around the position. With a
+    /// radius of zero, they all start at the position itself.
+    #[serde(default)]
+    pub spread: f32,
+}
+
+/// The creatures the WorldInitSystem spawns on the first frame, so a scenario always starts from
+/// the same known state. Loaded from `resources/world_init.ron`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct WorldInit {
+    pub entries: Vec<WorldInitEntry>,
+}
@@ -1,9 +1,22 @@
 use crate::{
     resources::{
         audio::initialise_audio,
+        camera::CameraConfig,
+        combat::CombatConfig,
+        despawn::DespawnConfig,
+        physics::PhysicsConfig,
         prefabs::{initialize_prefabs, update_prefabs},
+        replay::ReplayConfig,
+        species_color::SpeciesColor,
+        stats_export::StatsExportConfig,
+        terrain::Terrain,
+        timeline::Timeline,
+        topplegrass::TopplegrassConfig,
         wind::*,
+        wind_field::WindField,
+        wind_source::WindSourceConfig,
         world_bounds::WorldBounds,
+        world_init::WorldInit,
     },
     states::{main_game::MainGameState, menu::MenuState},
 };
@@ -57,6 +70,87 @@ impl SimpleState for LoadingState {
             Wind::default()
         });
         data.world.insert(wind_config);
+        let wind_field_path = self.config_path.clone() + "/wind_field.ron";
+        let wind_field = WindField::load(wind_field_path).unwrap_or_else(|error| {
+            error!("Failed to load wind field from config file. Using WindField::default() instead. Error: {:?}", error);
+            WindField::default()
+        });
+        data.world.insert(wind_field);
+        let wind_source_path = self.config_path.clone() + "/wind_source.ron";
+        let wind_source_config = WindSourceConfig::load(wind_source_path).unwrap_or_else(|error| {
+            error!("Failed to load wind source config from config file. Using WindSourceConfig::default() instead. Error: {:?}", error);
+            WindSourceConfig::default()
+        });
+        data.world.insert(wind_source_config);
+        let topplegrass_config_path = self.config_path.clone() + "/topplegrass.ron";
+        let topplegrass_config = TopplegrassConfig::load(topplegrass_config_path).unwrap_or_else(|error| {
+            error!("Failed to load topplegrass config from config file. Using TopplegrassConfig::default() instead. Error: {:?}", error);
+            TopplegrassConfig::default()
+        });
+        data.world.insert(topplegrass_config);
+        let physics_config_path = self.config_path.clone() + "/physics.ron";
+        let physics_config = PhysicsConfig::load(physics_config_path).unwrap_or_else(|error| {
+            error!("Failed to load physics config from config file. Using PhysicsConfig::default() instead. Error: {:?}", error);
+            PhysicsConfig::default()
+        });
+        data.world.insert(physics_config);
+        let terrain_path = self.config_path.clone() + "/terrain.ron";
+        let terrain = Terrain::load(terrain_path).unwrap_or_else(|error| {
+            error!(
+                "Failed to load terrain from config file. Using flat ground instead. Error: {:?}",
+                error
+            );
+            Terrain::default()
+        });
+        data.world.insert(terrain);
+        let despawn_config_path = self.config_path.clone() + "/despawn.ron";
+        let despawn_config = DespawnConfig::load(despawn_config_path).unwrap_or_else(|error| {
+            error!("Failed to load despawn config from config file. Using DespawnConfig::default() instead. Error: {:?}", error);
+            DespawnConfig::default()
+        });
+        data.world.insert(despawn_config);
+        let combat_config_path = self.config_path.clone() + "/combat.ron";
+        let combat_config = CombatConfig::load(combat_config_path).unwrap_or_else(|error| {
+            error!("Failed to load combat config from config file. Using CombatConfig::default() instead. Error: {:?}", error);
+            CombatConfig::default()
+        });
+        data.world.insert(combat_config);
+        let replay_config_path = self.config_path.clone() + "/replay.ron";
+        let replay_config = ReplayConfig::load(replay_config_path).unwrap_or_else(|error| {
+            error!("Failed to load replay config from config file. Using ReplayConfig::default() instead. Error: {:?}", error);
+            ReplayConfig::default()
+        });
+        data.world.insert(replay_config);
+        let timeline_path = self.config_path.clone() + "/timeline.ron";
+        let timeline = Timeline::load(timeline_path).unwrap_or_else(|error| {
+            error!("Failed to load timeline from config file. Using an empty Timeline instead. Error: {:?}", error);
+            Timeline::default()
+        });
+        data.world.insert(timeline);
+        let world_init_path = self.config_path.clone() + "/world_init.ron";
+        let world_init = WorldInit::load(world_init_path).unwrap_or_else(|error| {
+            error!("Failed to load world init from config file. Using an empty WorldInit instead. Error: {:?}", error);
+            WorldInit::default()
+        });
+        data.world.insert(world_init);
+        let species_colors_path = self.config_path.clone() + "/species_colors.ron";
+        let species_color = SpeciesColor::load(species_colors_path).unwrap_or_else(|error| {
+            error!("Failed to load species colors from config file. Using SpeciesColor::default() instead. Error: {:?}", error);
+            SpeciesColor::default()
+        });
+        data.world.insert(species_color);
+        let stats_export_path = self.config_path.clone() + "/stats_export.ron";
+        let stats_export_config = StatsExportConfig::load(stats_export_path).unwrap_or_else(|error| {
+            error!("Failed to load stats export config from config file. Using StatsExportConfig::default() instead. Error: {:?}", error);
+            StatsExportConfig::default()
+        });
+        data.world.insert(stats_export_config);
+        let camera_config_path = self.config_path.clone() + "/camera.ron";
+        let camera_config = CameraConfig::load(camera_config_path).unwrap_or_else(|error| {
+            error!("Failed to load camera config from config file. Using CameraConfig::default() instead. Error: {:?}", error);
+            CameraConfig::default()
+        });
+        data.world.insert(camera_config);
     }
 
     fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
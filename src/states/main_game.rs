@@ -12,23 +12,24 @@ use amethyst::{
         palette::rgb::{Srgb, Srgba},
         resources::AmbientColor,
     },
-    shrev::EventChannel,
+    shrev::{EventChannel, ReaderId},
     window::ScreenDimensions,
 };
 use std::f32;
 
 use crate::systems::behaviors::decision::{
-    ClosestSystem, Predator, Prey, QueryPredatorsAndPreySystem, SeekSystem,
+    ClosestSystem, Predator, Prey, QueryPredatorsAndPreySystem, SeekSystem, ThreatMemorySystem,
 };
 use crate::systems::behaviors::obstacle::{ClosestObstacleSystem, Obstacle};
 use crate::{
     components::creatures::CreatureTag,
     resources::{
-        debug::DebugConfig, prefabs::UiPrefabRegistry, spatial_grid::SpatialGrid,
-        world_bounds::WorldBounds,
+        debug::DebugConfig, physics::Physics, prefabs::UiPrefabRegistry, sandbox_mode::SandboxMode,
+        spatial_grid::SpatialGrid, spawn_stats::SpawnStats, spawning_enabled::SpawningEnabled,
+        topplegrass::TopplegrassConfig, world_bounds::WorldBounds,
     },
     states::pause_menu::PauseMenuState,
-    systems::*,
+    systems::{debug_input::DebugCommand, *},
 };
 use rand::{thread_rng, Rng};
 use std::f32::consts::PI;
@@ -42,6 +43,7 @@ pub struct MainGameState {
     ui_dispatcher: Dispatcher<'static, 'static>,
     ui: Option<Entity>,
     camera: Option<Entity>,
+    debug_command_reader: Option<ReaderId<DebugCommand>>,
     paused: bool,
     desired_time_scale: f32,
 }
@@ -54,11 +56,36 @@ impl MainGameState {
         MainGameState {
             dispatcher: DispatcherBuilder::new()
                 .with_pool(pool)
+                .with(
+                    debug_input::DebugInputSystem::default(),
+                    "debug_input_system",
+                    &[],
+                )
+                .with(
+                    replay::CommandPlaybackSystem::default(),
+                    "command_playback_system",
+                    &[],
+                )
+                .with(
+                    replay::CommandRecorderSystem::default(),
+                    "command_recorder_system",
+                    &["debug_input_system"],
+                )
                 .with(
                     camera_movement::CameraMovementSystem::default(),
                     "camera_movement",
                     &[],
                 )
+                .with(
+                    camera_movement::SwarmZoomSystem,
+                    "swarm_zoom",
+                    &["camera_movement"],
+                )
+                .with(
+                    camera_movement::CameraShakeSystem::default(),
+                    "camera_shake",
+                    &["camera_movement"],
+                )
                 .with(perception::SpatialGridSystem, "spatial_grid", &[])
                 .with(
                     perception::EntityDetectionSystem,
@@ -81,6 +108,11 @@ impl MainGameState {
                     "closest_predator_system",
                     &["query_predators_and_prey_system"],
                 )
+                .with(
+                    ThreatMemorySystem,
+                    "threat_memory_system",
+                    &["closest_predator_system"],
+                )
                 .with(
                     SeekSystem::<Prey>::new(
                         Rotation3::from_axis_angle(&Vector3::z_axis(), 0.0),
@@ -96,7 +128,7 @@ impl MainGameState {
                         1.0,
                     ),
                     "avoid_predator_system",
-                    &["closest_predator_system"],
+                    &["threat_memory_system"],
                 )
                 .with(
                     SeekSystem::<Obstacle>::new(
@@ -122,22 +154,78 @@ impl MainGameState {
                         "ricochet_system",
                     ],
                 )
+                .with(
+                    wind_migration::WindMigrationSystem::default(),
+                    "wind_migration_system",
+                    &["wander_system"],
+                )
+                .with(
+                    impulse::ImpulseSystem::default(),
+                    "impulse_system",
+                    &["wander_system", "wind_migration_system"],
+                )
+                .with(
+                    movement::SanityCheckSystem::default(),
+                    "sanity_check_system",
+                    &["wander_system", "wind_migration_system", "impulse_system"],
+                )
+                .with(
+                    movement::GlobalSpeedLimitSystem,
+                    "global_speed_limit_system",
+                    &["sanity_check_system"],
+                )
                 .with(
                     movement::MovementSystem,
                     "movement_system",
-                    &["wander_system"],
+                    &[
+                        "wander_system",
+                        "sanity_check_system",
+                        "global_speed_limit_system",
+                    ],
                 )
+                .with(growth::GrowthSystem, "growth_system", &[])
+                .with(size::SizeSystem, "size_system", &["growth_system"])
                 .with(
                     collision::CollisionSystem,
                     "collision_system",
                     &["movement_system"],
                 )
+                .with(
+                    collision::ObstacleCollisionSystem,
+                    "obstacle_collision_system",
+                    &["movement_system"],
+                )
                 .with(
                     collision::EnforceBoundsSystem,
                     "enforce_bounds_system",
                     &["movement_system"],
                 )
+                .with(
+                    collision::BoundsBounceSystem,
+                    "bounds_bounce_system",
+                    &["movement_system"],
+                )
+                .with(
+                    topplegrass_merge::TopplegrassMergeSystem::default(),
+                    "topplegrass_merge_system",
+                    &["collision_system"],
+                )
+                .with(
+                    topplegrass_knock::TopplegrassKnockSystem::default(),
+                    "topplegrass_knock_system",
+                    &["collision_system"],
+                )
                 .with(digestion::DigestionSystem, "digestion_system", &[])
+                .with(
+                    species_color::SpeciesColorSystem::default(),
+                    "species_color_system",
+                    &[],
+                )
+                .with(
+                    digestion::TintByEnergySystem,
+                    "tint_by_energy_system",
+                    &["digestion_system", "species_color_system"],
+                )
                 .with(
                     death::StarvationSystem,
                     "starvation_system",
@@ -147,7 +235,7 @@ impl MainGameState {
                 .with(
                     combat::FindAttackSystem::default(),
                     "find_attack_system",
-                    &["cooldown_system"],
+                    &["cooldown_system", "spatial_grid"],
                 )
                 .with(
                     combat::PerformDefaultAttackSystem::default(),
@@ -169,22 +257,114 @@ impl MainGameState {
                     "debug_spawn_trigger",
                     &[],
                 )
+                .with(timeline::TimelineSystem::default(), "timeline_system", &[])
+                .with(
+                    world_init::WorldInitSystem::default(),
+                    "world_init_system",
+                    &[],
+                )
+                .with(
+                    reset::ResetSystem::default(),
+                    "reset_system",
+                    &["debug_input_system"],
+                )
                 .with(
                     swarm_behavior::SwarmSpawnSystem::default(),
                     "swarm_spawn",
                     &[],
                 )
                 .with(
-                    topplegrass::TopplegrassSpawnSystem::default(),
+                    topplegrass::TopplegrassSpawnSystem::from_config(
+                        &world.read_resource::<TopplegrassConfig>(),
+                    ),
                     "topplegrass_spawn_system",
                     &[],
                 )
+                .with(
+                    topplegrass::SpawnVelocitySystem::default(),
+                    "spawn_velocity_system",
+                    &[],
+                )
+                .with(wind_hud::WindHudSystem::default(), "wind_hud_system", &[])
+                .with(
+                    wind_shadow::WindShadowSystem::default(),
+                    "wind_shadow_system",
+                    &[],
+                )
                 .with(
                     topplegrass::TopplingSystem::default(),
                     "toppling_system",
-                    &[],
+                    &["spawn_velocity_system", "wind_shadow_system"],
+                )
+                .with(
+                    wind_force::WindForceSystem::default(),
+                    "wind_force_system",
+                    &["wind_shadow_system"],
+                )
+                .with(
+                    fan::FanSystem::default(),
+                    "fan_system",
+                    &["wind_force_system"],
                 )
                 .with(gravity::GravitySystem::default(), "gravity_system", &[])
+                .with(
+                    spawn_animation::SpawnAnimationSystem::default(),
+                    "spawn_animation_system",
+                    &[],
+                )
+                .with(
+                    debris::DebrisEmitterSystem::default(),
+                    "debris_emitter_system",
+                    &[],
+                )
+                .with(
+                    debris::DebrisSpawnSystem::default(),
+                    "debris_spawn_system",
+                    &[],
+                )
+                .with(seeds::SeedDropSystem::default(), "seed_drop_system", &[])
+                .with(
+                    seeds::GerminationSystem::default(),
+                    "germination_system",
+                    &[],
+                )
+                .with(lifetime::LifetimeSystem::default(), "lifetime_system", &[])
+                .with(
+                    idle_despawn::IdleDespawnSystem::default(),
+                    "idle_despawn_system",
+                    &["movement_system"],
+                )
+                .with(
+                    distance_cull::DistanceCullSystem::default(),
+                    "distance_cull_system",
+                    &["camera_movement"],
+                )
+                .with(minimap::MinimapSystem::default(), "minimap_system", &[])
+                .with(
+                    stats_export::CreatureStatsExportSystem::default(),
+                    "creature_stats_export_system",
+                    &["movement_system"],
+                )
+                .with(
+                    picking::PickingSystem::default(),
+                    "picking_system",
+                    &["debug_input_system"],
+                )
+                .with(
+                    fan::FanPlacementSystem::default(),
+                    "fan_placement_system",
+                    &["debug_input_system"],
+                )
+                .with(
+                    rolling_sound::RollingSoundSystem::default(),
+                    "rolling_sound_system",
+                    &["movement_system"],
+                )
+                .with(
+                    scent::ScentSystem::default(),
+                    "scent_system",
+                    &["movement_system"],
+                )
                 .with(
                     out_of_bounds::OutOfBoundsDespawnSystem::default(),
                     "out_of_bounds_despawn_system",
@@ -193,7 +373,26 @@ impl MainGameState {
                 .with(
                     wind_control::DebugWindControlSystem::default(),
                     "wind_control_system",
-                    &[],
+                    &["debug_input_system", "command_playback_system"],
+                )
+                .with(
+                    wind_source::WindSourceSystem::default(),
+                    "wind_source_system",
+                    &["wind_control_system"],
+                )
+                .with(
+                    wind_magnitude::WindMagnitudeControllerSystem::default(),
+                    "wind_magnitude_controller_system",
+                    &[
+                        "wind_control_system",
+                        "wind_source_system",
+                        "timeline_system",
+                    ],
+                )
+                .with(
+                    wind_smoothing::WindSmoothingSystem::default(),
+                    "wind_smoothing_system",
+                    &["wind_magnitude_controller_system"],
                 )
                 .with(
                     swarm_behavior::SwarmBehaviorSystem::default(),
@@ -243,6 +442,16 @@ impl MainGameState {
                     "debug_entity_detection",
                     &["debug_system"],
                 )
+                .with(
+                    debug::BoundsWireframeSystem::default(),
+                    "bounds_wireframe_system",
+                    &[],
+                )
+                .with(
+                    debug::VelocityDebugSystem::default(),
+                    "velocity_debug_system",
+                    &[],
+                )
                 .build(),
             ui_dispatcher: DispatcherBuilder::new()
                 .with(
@@ -253,6 +462,7 @@ impl MainGameState {
                 .build(),
             ui: None,
             camera: None,
+            debug_command_reader: None,
             paused: false,
             desired_time_scale: 1.0,
         }
@@ -269,32 +479,59 @@ impl MainGameState {
             });
     }
 
-    fn handle_action(&mut self, action: &str, world: &mut World) -> SimpleTrans {
-        if action == "ToggleDebug" {
-            let mut debug_config = world.write_resource::<DebugConfig>();
-            debug_config.visible = !debug_config.visible;
-            Trans::None
-        } else if action == main_game_ui::PAUSE_BUTTON.action {
-            self.paused = !self.paused;
-            self.update_time_scale(world);
-            Trans::None
-        } else if action == main_game_ui::SPEED_UP_BUTTON.action {
-            self.desired_time_scale = clamp(
-                self.desired_time_scale * TIME_SCALE_FACTOR,
-                TIME_SCALE_RANGE.0,
-                TIME_SCALE_RANGE.1,
-            );
-            self.update_time_scale(world);
-            Trans::None
-        } else if action == main_game_ui::SLOW_DOWN_BUTTON.action {
-            self.desired_time_scale = clamp(
-                self.desired_time_scale / TIME_SCALE_FACTOR,
-                TIME_SCALE_RANGE.0,
-                TIME_SCALE_RANGE.1,
-            );
-            self.update_time_scale(world);
-            Trans::None
-        } else if action == main_game_ui::MENU_BUTTON.action {
+    fn handle_command(&mut self, command: DebugCommand, world: &mut World) {
+        match command {
+            DebugCommand::ToggleDebug => {
+                let mut debug_config = world.write_resource::<DebugConfig>();
+                debug_config.visible = !debug_config.visible;
+            }
+            DebugCommand::ToggleSandbox => {
+                let mut sandbox_mode = world.write_resource::<SandboxMode>();
+                sandbox_mode.0 = !sandbox_mode.0;
+            }
+            DebugCommand::ToggleSpawning => {
+                let mut spawning_enabled = world.write_resource::<SpawningEnabled>();
+                spawning_enabled.0 = !spawning_enabled.0;
+                info!("Topplegrass spawning enabled: {}", spawning_enabled.0);
+            }
+            DebugCommand::ToggleBoundsWireframe => {
+                let mut debug_config = world.write_resource::<DebugConfig>();
+                debug_config.show_bounds = !debug_config.show_bounds;
+            }
+            DebugCommand::ToggleVelocities => {
+                let mut debug_config = world.write_resource::<DebugConfig>();
+                debug_config.show_velocities = !debug_config.show_velocities;
+            }
+            DebugCommand::ToggleGravity => {
+                let mut physics = world.write_resource::<Physics>();
+                physics.gravity_enabled = !physics.gravity_enabled;
+            }
+            DebugCommand::TogglePause => {
+                self.paused = !self.paused;
+                self.update_time_scale(world);
+            }
+            DebugCommand::SpeedUp => {
+                self.desired_time_scale = clamp(
+                    self.desired_time_scale * TIME_SCALE_FACTOR,
+                    TIME_SCALE_RANGE.0,
+                    TIME_SCALE_RANGE.1,
+                );
+                self.update_time_scale(world);
+            }
+            DebugCommand::SlowDown => {
+                self.desired_time_scale = clamp(
+                    self.desired_time_scale / TIME_SCALE_FACTOR,
+                    TIME_SCALE_RANGE.0,
+                    TIME_SCALE_RANGE.1,
+                );
+                self.update_time_scale(world);
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_action(&mut self, action: &str) -> SimpleTrans {
+        if action == main_game_ui::MENU_BUTTON.action {
             Trans::Push(Box::new(PauseMenuState::default()))
         } else {
             Trans::None
@@ -303,13 +540,13 @@ impl MainGameState {
 }
 
 impl SimpleState for MainGameState {
-    fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
+    fn handle_event(&mut self, _data: StateData<GameData>, event: StateEvent) -> SimpleTrans {
         match event {
             StateEvent::Window(_) => Trans::None, // Events related to the window and inputs.
             StateEvent::Ui(_) => Trans::None,     // Ui event. Button presses, mouse hover, etc...
             StateEvent::Input(input_event) => {
                 if let InputEvent::ActionPressed(action) = input_event {
-                    self.handle_action(&action, data.world)
+                    self.handle_action(&action)
                 } else {
                     Trans::None
                 }
@@ -323,10 +560,16 @@ impl SimpleState for MainGameState {
         self.dispatcher.setup(data.world);
         self.debug_dispatcher.setup(data.world);
         self.ui_dispatcher.setup(data.world);
+        self.debug_command_reader = Some(
+            data.world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
 
         // Setup debug config resource
         data.world.insert(DebugConfig::default());
         data.world.insert(SpatialGrid::new(1.0f32));
+        data.world.insert(SpawnStats::default());
 
         // main game ui
         let ui_prefab = data
@@ -490,17 +733,18 @@ impl SimpleState for MainGameState {
     fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
         self.dispatcher.dispatch(&data.world);
 
-        for (db_comp,) in (&mut data.world.write_storage::<DebugLinesComponent>(),).join() {
-            db_comp.clear();
-        }
-        let show_debug = {
-            let debug_config = data.world.read_resource::<DebugConfig>();
-            debug_config.visible
-        };
-        if show_debug {
-            self.debug_dispatcher.dispatch(&data.world);
+        let commands: Vec<DebugCommand> = data
+            .world
+            .fetch::<EventChannel<DebugCommand>>()
+            .read(self.debug_command_reader.as_mut().unwrap())
+            .cloned()
+            .collect();
+        for command in commands {
+            self.handle_command(command, data.world);
         }
 
+        draw_debug_lines(&mut self.debug_dispatcher, &data.world);
+
         data.data.update(&data.world);
 
         self.ui_dispatcher.dispatch(&data.world);
@@ -508,3 +752,55 @@ impl SimpleState for MainGameState {
         Trans::None
     }
 }
+
+/// Clears the debug lines drawn last frame, and lets the systems of the debug_dispatcher draw
+/// this frame's lines while the debug view is visible. Debug lines drawn anywhere else are wiped
+/// before they are rendered.
+fn draw_debug_lines(debug_dispatcher: &mut Dispatcher<'static, 'static>, world: &World) {
+    for (db_comp,) in (&mut world.write_storage::<DebugLinesComponent>(),).join() {
+        db_comp.clear();
+    }
+    let show_debug = {
+        let debug_config = world.read_resource::<DebugConfig>();
+        debug_config.visible
+    };
+    if show_debug {
+        debug_dispatcher.dispatch(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_wireframe_survives_to_render_every_frame() {
+        let mut world = World::new();
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        let mut debug_dispatcher = DispatcherBuilder::new()
+            .with(
+                debug::BoundsWireframeSystem::default(),
+                "bounds_wireframe_system",
+                &[],
+            )
+            .build();
+        debug_dispatcher.setup(&mut world);
+        {
+            let mut debug_config = world.write_resource::<DebugConfig>();
+            debug_config.visible = true;
+            debug_config.show_bounds = true;
+        }
+        let empty = format!("{:?}", DebugLinesComponent::new());
+
+        for _ in 0..3 {
+            draw_debug_lines(&mut debug_dispatcher, &world);
+            world.maintain();
+
+            let lines = world.read_storage::<DebugLinesComponent>();
+            assert_eq!(lines.join().count(), 1);
+            for drawn in lines.join() {
+                assert_ne!(format!("{:?}", drawn), empty);
+            }
+        }
+    }
+}
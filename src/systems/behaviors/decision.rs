@@ -161,6 +161,44 @@ where
     }
 }
 
+/// Remembers where creatures with a `ThreatMemory` last saw their closest predator. While no
+/// predator is in sight, the remembered position is attached as `Closest<Predator>` instead, so
+/// the creature keeps fleeing from it until the memory times out. Should run after the
+/// `ClosestSystem<Predator>` and before the system that flees from predators.
+pub struct ThreatMemorySystem;
+impl<'s> System<'s> for ThreatMemorySystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, ThreatMemory>,
+        WriteStorage<'s, Closest<Predator>>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, transforms, mut memories, mut closest_predators, time): Self::SystemData,
+    ) {
+        for (entity, transform, memory) in (&entities, &transforms, &mut memories).join() {
+            let position = transform.translation();
+            if let Some(closest) = closest_predators.get(entity) {
+                memory.last_seen = Some(position + closest.distance);
+                memory.timer = memory.duration;
+                continue;
+            }
+            memory.timer -= time.delta_seconds();
+            if memory.timer <= 0.0 {
+                memory.last_seen = None;
+            }
+            if let Some(last_seen) = memory.last_seen {
+                closest_predators
+                    .insert(entity, Closest::new(last_seen - position))
+                    .expect("unreachable: we just queried");
+            }
+        }
+    }
+}
+
 /// Seek out the entity referenced by `Closest<T>` and apply a steering force
 /// towards that entity. The steering force can be modified using the `attraction_modifier` factor.
 /// By setting `attraction_modifier` to `-1` this system will behave like `Evade`.
@@ -203,3 +241,56 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creature_keeps_fleeing_until_it_forgets_the_threat() {
+        let mut world = World::new();
+        let mut memory_system = ThreatMemorySystem;
+        let mut flee_system = SeekSystem::<Predator>::new(
+            Rotation3::from_axis_angle(&Vector3::z_axis(), f32::consts::PI),
+            1.0,
+        );
+        System::setup(&mut memory_system, &mut world);
+        System::setup(&mut flee_system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+        let creature = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 10.0,
+            })
+            .with(ThreatMemory {
+                duration: 1.05,
+                ..Default::default()
+            })
+            .build();
+        // A predator is seen to the east once, then disappears from sight.
+        world
+            .write_storage::<Closest<Predator>>()
+            .insert(creature, Closest::new(Vector3::new(2.0, 0.0, 0.0)))
+            .unwrap();
+
+        let mut fleeing_frames = 0;
+        for _ in 0..20 {
+            memory_system.run_now(&world);
+            flee_system.run_now(&world);
+            if world
+                .write_storage::<Closest<Predator>>()
+                .remove(creature)
+                .is_some()
+            {
+                fleeing_frames += 1;
+            }
+        }
+
+        // The predator was in sight for one frame, then remembered for ten more.
+        assert_eq!(fleeing_frames, 11);
+        let movements = world.read_storage::<Movement>();
+        assert!(movements.get(creature).unwrap().velocity.x < 0.0);
+    }
+}
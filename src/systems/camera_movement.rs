@@ -1,10 +1,18 @@
 use amethyst::{
-    core::{Named, Time, Transform},
+    core::{math::Vector2, Named, Time, Transform},
     ecs::*,
     input::{InputHandler, StringBindings},
     renderer::camera::Camera,
 };
 
+use rand::{Rng, RngCore};
+use std::f32::consts::PI;
+
+use crate::{
+    components::creatures::TopplegrassTag,
+    resources::{camera::CameraConfig, game_rng::EffectsRng, wind::Wind},
+};
+
 #[derive(Default)]
 pub struct CameraMovementSystem {}
 
@@ -44,3 +52,206 @@ impl<'s> System<'s> for CameraMovementSystem {
         }
     }
 }
+
+/// Zooms the main camera out when the topplegrass swarm spreads out, and back in when it
+/// clusters together, if enabled in the CameraConfig. The zoom follows the spread of the swarm
+/// smoothly, and stays between CameraConfig::min_zoom and CameraConfig::max_zoom.
+#[derive(Default)]
+pub struct SwarmZoomSystem;
+
+impl<'s> System<'s> for SwarmZoomSystem {
+    type SystemData = (
+        WriteStorage<'s, Camera>,
+        ReadStorage<'s, Named>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, TopplegrassTag>,
+        Read<'s, CameraConfig>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (mut cameras, names, transforms, topple_tags, config, time): Self::SystemData,
+    ) {
+        if !config.zoom_to_swarm {
+            return;
+        }
+        let positions = (&transforms, &topple_tags)
+            .join()
+            .map(|(transform, _)| transform.translation().xy())
+            .collect::<Vec<Vector2<f32>>>();
+        if positions.is_empty() {
+            return;
+        }
+        let target = swarm_zoom(bounding_radius(&positions), &config);
+        for (camera, name) in (&mut cameras, &names).join() {
+            if name.name != "Main camera" {
+                continue;
+            }
+            if let Some(orthographic) = camera.projection_mut().as_orthographic_mut() {
+                let current = orthographic.top();
+                let aspect_ratio = orthographic.right() / current;
+                let zoom =
+                    smooth_zoom(current, target, config.zoom_rate, time.delta_real_seconds());
+                orthographic.set_bottom_and_top(-zoom, zoom);
+                orthographic.set_left_and_right(-zoom * aspect_ratio, zoom * aspect_ratio);
+            }
+        }
+    }
+}
+
+/// Shakes the main camera by a small random offset each frame while the wind is stronger than
+/// CameraConfig::shake_threshold, if enabled in the CameraConfig. The shake grows with the wind
+/// speed up to CameraConfig::shake_max_offset, and builds up and dies down smoothly. The offset
+/// of the previous frame is taken out again first, so the camera never drifts away.
+pub struct CameraShakeSystem {
+    amplitude: f32,
+    offset: Vector2<f32>,
+}
+
+impl Default for CameraShakeSystem {
+    fn default() -> Self {
+        CameraShakeSystem {
+            amplitude: 0.0,
+            offset: Vector2::zeros(),
+        }
+    }
+}
+
+impl<'s> System<'s> for CameraShakeSystem {
+    type SystemData = (
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Named>,
+        WriteStorage<'s, Transform>,
+        Read<'s, Wind>,
+        Read<'s, CameraConfig>,
+        Read<'s, Time>,
+        Write<'s, EffectsRng>,
+    );
+
+    fn run(
+        &mut self,
+        (cameras, names, mut transforms, wind, config, time, mut rng): Self::SystemData,
+    ) {
+        let target = if config.shake_enabled {
+            shake_amplitude(wind.wind.magnitude(), &config)
+        } else {
+            0.0
+        };
+        let blend = 1.0 - (-config.shake_rate * time.delta_real_seconds()).exp();
+        self.amplitude += (target - self.amplitude) * blend;
+        let offset = if self.amplitude > 1e-4 {
+            shake_offset(self.amplitude, &mut rng.0)
+        } else {
+            Vector2::zeros()
+        };
+        if offset == self.offset {
+            return;
+        }
+        for (_, name, transform) in (&cameras, &names, &mut transforms).join() {
+            if name.name == "Main camera" {
+                transform.prepend_translation_x(offset.x - self.offset.x);
+                transform.prepend_translation_y(offset.y - self.offset.y);
+            }
+        }
+        self.offset = offset;
+    }
+}
+
+/// Returns how far the camera shakes in wind of the given speed.
+pub fn shake_amplitude(wind_speed: f32, config: &CameraConfig) -> f32 {
+    ((wind_speed - config.shake_threshold).max(0.0) * config.shake_strength)
+        .min(config.shake_max_offset)
+}
+
+/// Returns a camera offset of the given length in a random direction.
+fn shake_offset(amplitude: f32, rng: &mut dyn RngCore) -> Vector2<f32> {
+    let angle = rng.gen_range(0.0, 2.0 * PI);
+    Vector2::new(angle.cos(), angle.sin()) * amplitude
+}
+
+/// Returns the distance from the centroid of the positions to the one furthest away from it.
+pub fn bounding_radius(positions: &[Vector2<f32>]) -> f32 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let centroid = positions.iter().sum::<Vector2<f32>>() / positions.len() as f32;
+    positions
+        .iter()
+        .map(|position| (position - centroid).magnitude())
+        .fold(0.0, f32::max)
+}
+
+/// Returns the half height of the view that keeps a swarm with the given bounding radius in
+/// frame.
+pub fn swarm_zoom(radius: f32, config: &CameraConfig) -> f32 {
+    (radius * config.zoom_padding)
+        .max(config.min_zoom)
+        .min(config.max_zoom)
+}
+
+/// Moves the zoom from current towards target, covering a larger part of the distance the higher
+/// the rate and the longer the frame.
+fn smooth_zoom(current: f32, target: f32, rate: f32, delta_seconds: f32) -> f32 {
+    current + (target - current) * (1.0 - (-rate * delta_seconds).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::game_rng::GameRng;
+
+    fn ring(radius: f32) -> Vec<Vector2<f32>> {
+        vec![
+            Vector2::new(radius, 0.0),
+            Vector2::new(-radius, 0.0),
+            Vector2::new(0.0, radius),
+            Vector2::new(0.0, -radius),
+        ]
+    }
+
+    #[test]
+    fn wider_spread_zooms_out() {
+        let config = CameraConfig::default();
+        let clustered = swarm_zoom(bounding_radius(&ring(4.0)), &config);
+        let spread = swarm_zoom(bounding_radius(&ring(8.0)), &config);
+        assert!(spread > clustered);
+
+        // Always within the configured bounds.
+        assert_eq!(swarm_zoom(bounding_radius(&ring(0.1)), &config), 4.0);
+        assert_eq!(swarm_zoom(bounding_radius(&ring(100.0)), &config), 20.0);
+    }
+
+    #[test]
+    fn shake_scales_with_wind_above_the_threshold() {
+        let config = CameraConfig {
+            shake_enabled: true,
+            ..Default::default()
+        };
+        let mut rng = GameRng::seeded(7);
+        let offset_length = |wind_speed: f32, rng: &mut GameRng| {
+            shake_offset(shake_amplitude(wind_speed, &config), rng).magnitude()
+        };
+
+        assert_eq!(offset_length(2.0, &mut rng), 0.0);
+        assert_eq!(offset_length(config.shake_threshold, &mut rng), 0.0);
+        let moderate = offset_length(5.0, &mut rng);
+        let strong = offset_length(6.0, &mut rng);
+        assert!(moderate > 0.0);
+        assert!(strong > moderate);
+        assert!(offset_length(100.0, &mut rng) <= config.shake_max_offset + 1e-6);
+    }
+
+    #[test]
+    fn zoom_approaches_the_target_smoothly() {
+        let mut zoom = 5.0;
+        let mut previous_step = f32::MAX;
+        for _ in 0..10 {
+            let next = smooth_zoom(zoom, 10.0, 1.0, 0.1);
+            assert!(next > zoom && next < 10.0);
+            assert!(next - zoom < previous_step);
+            previous_step = next - zoom;
+            zoom = next;
+        }
+    }
+}
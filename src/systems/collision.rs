@@ -1,6 +1,10 @@
 use amethyst::renderer::{debug_drawing::DebugLinesComponent, palette::Srgba};
 use amethyst::shrev::{EventChannel, ReaderId};
-use amethyst::{core::math::Point3, core::Transform, ecs::prelude::*};
+use amethyst::{
+    core::math::{Point3, Vector2, Vector3},
+    core::Transform,
+    ecs::prelude::*,
+};
 use log::info;
 use std::f32;
 #[cfg(feature = "profiler")]
@@ -37,15 +41,113 @@ impl<'s> System<'s> for EnforceBoundsSystem {
     }
 }
 
+/// Makes entities with BounceBounds bounce off the world bounds. When such an entity crosses an
+/// edge, it is moved back inside and its velocity perpendicular to that edge is reflected.
+pub struct BoundsBounceSystem;
+
+impl<'s> System<'s> for BoundsBounceSystem {
+    type SystemData = (
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, creatures::Movement>,
+        ReadStorage<'s, creatures::BounceBounds>,
+        ReadExpect<'s, WorldBounds>,
+    );
+
+    fn run(&mut self, (mut locals, mut movements, bounces, bounds): Self::SystemData) {
+        for (local, movement, _) in (&mut locals, &mut movements, &bounces).join() {
+            let pos = local.translation_mut();
+            let velocity = &mut movement.velocity;
+            if pos.x > bounds.right {
+                pos.x = bounds.right;
+                velocity.x = -velocity.x.abs();
+            } else if pos.x < bounds.left {
+                pos.x = bounds.left;
+                velocity.x = velocity.x.abs();
+            }
+
+            if pos.y > bounds.top {
+                pos.y = bounds.top;
+                velocity.y = -velocity.y.abs();
+            } else if pos.y < bounds.bottom {
+                pos.y = bounds.bottom;
+                velocity.y = velocity.y.abs();
+            }
+        }
+    }
+}
+
+/// Keeps moving entities out of obstacles. An entity that overlaps an obstacle in the xy plane is
+/// pushed out along the collision normal, and the part of its velocity pointing into the obstacle
+/// is removed, so it slides around the obstacle instead of passing through. Entities without a
+/// Circle collider are treated as points.
+pub struct ObstacleCollisionSystem;
+
+impl<'s> System<'s> for ObstacleCollisionSystem {
+    type SystemData = (
+        ReadStorage<'s, collider::Obstacle>,
+        ReadStorage<'s, collider::Circle>,
+        WriteStorage<'s, creatures::Movement>,
+        WriteStorage<'s, Transform>,
+    );
+
+    fn run(&mut self, (obstacles, circles, mut movements, mut locals): Self::SystemData) {
+        let obstacle_circles: Vec<(Vector2<f32>, f32)> = (&obstacles, &locals)
+            .join()
+            .map(|(obstacle, local)| (local.translation().xy(), obstacle.collider.radius))
+            .collect();
+        if obstacle_circles.is_empty() {
+            return;
+        }
+        for (movement, local, circle, _) in
+            (&mut movements, &mut locals, circles.maybe(), !&obstacles).join()
+        {
+            let radius = circle.map_or(0.0, |circle| circle.radius);
+            for (obstacle_position, obstacle_radius) in &obstacle_circles {
+                let allowed_distance = radius + obstacle_radius;
+                let offset = local.translation().xy() - obstacle_position;
+                if offset.magnitude_squared() >= allowed_distance * allowed_distance {
+                    continue;
+                }
+                // An entity right at the center of the obstacle is pushed back the way it came.
+                let normal = if offset.magnitude() > f32::EPSILON {
+                    offset.normalize()
+                } else if movement.velocity.xy().magnitude() > f32::EPSILON {
+                    -movement.velocity.xy().normalize()
+                } else {
+                    Vector2::x()
+                };
+                let position = obstacle_position + normal * allowed_distance;
+                local.translation_mut().x = position.x;
+                local.translation_mut().y = position.y;
+                let inward_speed = movement.velocity.xy().dot(&normal);
+                if inward_speed < 0.0 {
+                    movement.velocity.x -= normal.x * inward_speed;
+                    movement.velocity.y -= normal.y * inward_speed;
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CollisionEvent {
     pub entity_a: Entity,
     pub entity_b: Entity,
+    /// The velocity entity_a hit entity_b with, before the CollisionSystem deflected it.
+    pub impact_velocity: Vector3<f32>,
 }
 
 impl CollisionEvent {
-    pub fn new(entity_a: Entity, entity_b: Entity) -> CollisionEvent {
-        CollisionEvent { entity_a, entity_b }
+    pub fn new(
+        entity_a: Entity,
+        entity_b: Entity,
+        impact_velocity: Vector3<f32>,
+    ) -> CollisionEvent {
+        CollisionEvent {
+            entity_a,
+            entity_b,
+            impact_velocity,
+        }
     }
 }
 
@@ -72,6 +174,7 @@ impl<'s> System<'s> for CollisionSystem {
         for (circle_a, movement, local_a, entity_a) in
             (&circles, &mut movements, &locals, &entities).join()
         {
+            let impact_velocity = movement.velocity;
             for (circle_b, local_b, entity_b) in (&circles, &locals, &entities).join() {
                 if entity_a == entity_b {
                     continue;
@@ -80,7 +183,11 @@ impl<'s> System<'s> for CollisionSystem {
                 let allowed_distance = circle_a.radius + circle_b.radius;
                 let direction = local_a.translation() - local_b.translation();
                 if direction.magnitude_squared() < allowed_distance * allowed_distance {
-                    collision_events.single_write(CollisionEvent::new(entity_a, entity_b));
+                    collision_events.single_write(CollisionEvent::new(
+                        entity_a,
+                        entity_b,
+                        impact_velocity,
+                    ));
 
                     if direction.magnitude() < f32::EPSILON {
                         movement.velocity = -movement.velocity;
@@ -145,3 +252,113 @@ impl<'s> System<'s> for DebugCollisionEventSystem {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing_the_right_edge_bounces_back() {
+        let mut world = World::new();
+        let mut system = BoundsBounceSystem;
+        System::setup(&mut system, &mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(10.5, 2.0, 0.0);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(creatures::Movement {
+                velocity: Vector3::new(3.0, 1.0, 0.0),
+                max_movement_speed: 5.0,
+            })
+            .with(creatures::BounceBounds)
+            .build();
+
+        system.run_now(&world);
+
+        let movements = world.read_storage::<creatures::Movement>();
+        let velocity = movements.get(entity).unwrap().velocity;
+        assert_eq!(velocity, Vector3::new(-3.0, 1.0, 0.0));
+        let transforms = world.read_storage::<Transform>();
+        let translation = transforms.get(entity).unwrap().translation();
+        assert_eq!(*translation, Vector3::new(10.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn moving_into_an_obstacle_stops_and_displaces() {
+        let mut world = World::new();
+        let mut system = ObstacleCollisionSystem;
+        System::setup(&mut system, &mut world);
+        world
+            .create_entity()
+            .with(Transform::default())
+            .with(collider::Obstacle {
+                collider: collider::Circle { radius: 1.5 },
+            })
+            .build();
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(1.5, 0.0, 0.5);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(collider::Circle { radius: 0.5 })
+            .with(creatures::Movement {
+                velocity: Vector3::new(-2.0, 1.0, 0.0),
+                max_movement_speed: 5.0,
+            })
+            .build();
+
+        system.run_now(&world);
+
+        // The velocity into the obstacle is gone, the velocity along its edge is kept.
+        let movements = world.read_storage::<creatures::Movement>();
+        let velocity = movements.get(entity).unwrap().velocity;
+        assert!((velocity - Vector3::new(0.0, 1.0, 0.0)).magnitude() < 1e-6);
+        let transforms = world.read_storage::<Transform>();
+        let translation = transforms.get(entity).unwrap().translation();
+        assert!((translation - Vector3::new(2.0, 0.0, 0.5)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn every_collision_reports_the_velocity_from_before_the_deflections() {
+        let mut world = World::new();
+        let mut system = CollisionSystem;
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<CollisionEvent>>()
+            .register_reader();
+        let spawn_at = |world: &mut World, x: f32, y: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, y, 0.0);
+            world
+                .create_entity()
+                .with(transform)
+                .with(collider::Circle { radius: 0.5 })
+                .build()
+        };
+        let mover = spawn_at(&mut world, 0.0, 0.0);
+        spawn_at(&mut world, 0.8, 0.0);
+        spawn_at(&mut world, 0.0, 0.8);
+        world
+            .write_storage::<creatures::Movement>()
+            .insert(
+                mover,
+                creatures::Movement {
+                    velocity: Vector3::new(1.0, 1.0, 0.0),
+                    max_movement_speed: 5.0,
+                },
+            )
+            .unwrap();
+
+        system.run_now(&world);
+
+        let impacts: Vec<Vector3<f32>> = world
+            .fetch::<EventChannel<CollisionEvent>>()
+            .read(&mut reader)
+            .filter(|event| event.entity_a == mover)
+            .map(|event| event.impact_velocity)
+            .collect();
+        assert_eq!(impacts, vec![Vector3::new(1.0, 1.0, 0.0); 2]);
+    }
+}
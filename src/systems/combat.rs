@@ -1,12 +1,15 @@
 use amethyst::{
-    core::Time,
+    core::{Time, Transform},
     ecs::*,
     shrev::{EventChannel, ReaderId},
 };
 
+use crate::components::collider::Circle;
 use crate::components::combat;
 use crate::components::combat::{Cooldown, Damage, Health, Speed};
+use crate::components::creatures::Size;
 use crate::components::digestion::{Fullness, Nutrition};
+use crate::resources::{combat::CombatConfig, spatial_grid::SpatialGrid};
 use crate::systems::collision::CollisionEvent;
 //#[cfg(test)]
 //use amethyst::Error;
@@ -47,6 +50,15 @@ pub struct AttackEvent {
     pub defender: Entity,
 }
 
+/// Emitted whenever a creature gains energy by eating another: amount is the nutrition that went
+/// from the prey to the fullness of the predator. Can be aggregated into a food web.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyTransferEvent {
+    pub from: Entity,
+    pub to: Entity,
+    pub amount: f32,
+}
+
 #[derive(Default)]
 pub struct PerformDefaultAttackSystem {
     event_reader: Option<ReaderId<AttackEvent>>,
@@ -61,6 +73,7 @@ impl<'s> System<'s> for PerformDefaultAttackSystem {
         WriteStorage<'s, Health>,
         WriteStorage<'s, Fullness>,
         WriteStorage<'s, Nutrition>,
+        Write<'s, EventChannel<EnergyTransferEvent>>,
     );
 
     fn run(
@@ -73,6 +86,7 @@ impl<'s> System<'s> for PerformDefaultAttackSystem {
             mut healths,
             mut fullnesses,
             mut nutritions,
+            mut energy_transfer_events,
         ): Self::SystemData,
     ) {
         let event_reader = self
@@ -102,6 +116,13 @@ impl<'s> System<'s> for PerformDefaultAttackSystem {
                     let delta = nutrition.value.min(damage.damage);
                     nutrition.value = nutrition.value - delta;
                     fullness.value = fullness.value + delta;
+                    if delta > 0.0 {
+                        energy_transfer_events.single_write(EnergyTransferEvent {
+                            from: event.defender,
+                            to: event.attacker,
+                            amount: delta,
+                        });
+                    }
                 }
             }
 
@@ -129,23 +150,52 @@ pub struct FindAttackSystem {
 }
 
 // Determine if a collision will trigger an attack. If that is the case, generate an `AttackEvent`
+// Predators with a `Size` also catch prey within their capture range without touching it. Every
+// predator attacks each of its prey at most once per frame, even if it both touches the prey and
+// has it in range.
 impl<'s> System<'s> for FindAttackSystem {
     type SystemData = (
+        Entities<'s>,
         Read<'s, EventChannel<CollisionEvent>>,
         Write<'s, EventChannel<AttackEvent>>,
         ReadStorage<'s, combat::HasFaction<Entity>>,
         ReadStorage<'s, combat::FactionPrey<Entity>>,
+        ReadStorage<'s, Cooldown>,
+        ReadStorage<'s, Size>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, Transform>,
+        ReadExpect<'s, SpatialGrid>,
+        Read<'s, CombatConfig>,
     );
 
     fn run(
         &mut self,
-        (collision_events, mut attack_events, has_faction, faction_preys): Self::SystemData,
+        (
+            entities,
+            collision_events,
+            mut attack_events,
+            has_faction,
+            faction_preys,
+            cooldowns,
+            sizes,
+            circles,
+            transforms,
+            grid,
+            config,
+        ): Self::SystemData,
     ) {
         let event_reader = self
             .event_reader
             .as_mut()
             .expect("`FindAttackSystem::setup` was not called before `FindAttackSystem::run`");
 
+        let mut attacks: Vec<(Entity, Entity)> = Vec::new();
+        let mut attack = |attacker: Entity, defender: Entity| {
+            if !attacks.contains(&(attacker, defender)) {
+                attacks.push((attacker, defender));
+            }
+        };
+
         for event in collision_events.read(event_reader) {
             let opt_factions = has_faction
                 .get(event.entity_a)
@@ -155,24 +205,45 @@ impl<'s> System<'s> for FindAttackSystem {
                 let preys_a = faction_preys.get(faction_a.faction);
                 if let Some(preys) = preys_a {
                     if preys.is_prey(&faction_b.faction) {
-                        attack_events.single_write(AttackEvent {
-                            attacker: event.entity_a,
-                            defender: event.entity_b,
-                        });
+                        attack(event.entity_a, event.entity_b);
                     }
                 }
 
                 let preys_b = faction_preys.get(faction_b.faction);
                 if let Some(preys) = preys_b {
                     if preys.is_prey(&faction_a.faction) {
-                        attack_events.single_write(AttackEvent {
-                            attacker: event.entity_b,
-                            defender: event.entity_a,
-                        });
+                        attack(event.entity_b, event.entity_a);
                     }
                 }
             }
         }
+
+        for (attacker, faction, size, transform, _) in
+            (&entities, &has_faction, &sizes, &transforms, !&cooldowns).join()
+        {
+            let preys = match faction_preys.get(faction.faction) {
+                Some(preys) => preys,
+                None => continue,
+            };
+            let range = capture_range(size, config.capture_radius_multiplier);
+            let nearby_entities = grid.query(transform, range);
+            for (defender, defender_faction, defender_transform, _) in
+                (&entities, &has_faction, &transforms, &nearby_entities).join()
+            {
+                if attacker == defender || !preys.is_prey(&defender_faction.faction) {
+                    continue;
+                }
+                let reach = range + circles.get(defender).map_or(0.0, |circle| circle.radius);
+                let distance = transform.translation() - defender_transform.translation();
+                if distance.magnitude_squared() < reach * reach {
+                    attack(attacker, defender);
+                }
+            }
+        }
+
+        for (attacker, defender) in attacks {
+            attack_events.single_write(AttackEvent { attacker, defender });
+        }
     }
 
     fn setup(&mut self, world: &mut World) {
@@ -185,6 +256,131 @@ impl<'s> System<'s> for FindAttackSystem {
     }
 }
 
+/// The distance from which a predator of the given size can catch its prey.
+pub fn capture_range(size: &Size, multiplier: f32) -> f32 {
+    size.value * multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn eating_prey_emits_an_energy_transfer_event() {
+        let mut world = World::new();
+        let mut system = PerformDefaultAttackSystem::default();
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<EnergyTransferEvent>>()
+            .register_reader();
+        let predator = world
+            .create_entity()
+            .with(Damage { damage: 3.0 })
+            .with(Fullness {
+                max: 10.0,
+                value: 1.0,
+            })
+            .build();
+        let prey = world.create_entity().with(Nutrition { value: 2.0 }).build();
+        world
+            .write_resource::<EventChannel<AttackEvent>>()
+            .single_write(AttackEvent {
+                attacker: predator,
+                defender: prey,
+            });
+
+        system.run_now(&world);
+
+        // The prey only had 2.0 nutrition left, less than the predator's damage.
+        let transfers: Vec<EnergyTransferEvent> = world
+            .fetch::<EventChannel<EnergyTransferEvent>>()
+            .read(&mut reader)
+            .cloned()
+            .collect();
+        assert_eq!(
+            transfers,
+            vec![EnergyTransferEvent {
+                from: prey,
+                to: predator,
+                amount: 2.0,
+            }]
+        );
+        assert_eq!(
+            world
+                .read_storage::<Fullness>()
+                .get(predator)
+                .unwrap()
+                .value,
+            3.0
+        );
+    }
+
+    #[test]
+    fn predators_attack_touching_prey_and_prey_in_range_once() {
+        let mut world = World::new();
+        world.insert(SpatialGrid::new(1.0));
+        let mut system = FindAttackSystem::default();
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<AttackEvent>>()
+            .register_reader();
+        let predators = world.create_entity().build();
+        let preys = world.create_entity().build();
+        world
+            .write_storage::<combat::FactionPrey<Entity>>()
+            .insert(predators, combat::FactionPrey { preys: vec![preys] })
+            .unwrap();
+        let spawn_at = |world: &mut World, faction: Entity, x: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, 0.0, 0.0);
+            transform.copy_local_to_global();
+            let entity = world
+                .create_entity()
+                .with(transform.clone())
+                .with(combat::HasFaction { faction })
+                .with(Circle { radius: 0.1 })
+                .build();
+            world
+                .write_resource::<SpatialGrid>()
+                .insert(entity, &transform);
+            entity
+        };
+        let predator = spawn_at(&mut world, predators, 0.0);
+        let touching = spawn_at(&mut world, preys, 0.2);
+        let in_range = spawn_at(&mut world, preys, 0.6);
+        let out_of_range = spawn_at(&mut world, preys, 2.0);
+        world
+            .write_storage::<Size>()
+            .insert(predator, Size { value: 0.4 })
+            .unwrap();
+        world
+            .write_resource::<EventChannel<CollisionEvent>>()
+            .single_write(CollisionEvent::new(predator, touching, Vector3::zeros()));
+
+        system.run_now(&world);
+
+        let attacks: Vec<(Entity, Entity)> = world
+            .fetch::<EventChannel<AttackEvent>>()
+            .read(&mut reader)
+            .map(|event| (event.attacker, event.defender))
+            .collect();
+        assert_eq!(attacks, vec![(predator, touching), (predator, in_range)]);
+        assert!(!attacks
+            .iter()
+            .any(|(_, defender)| *defender == out_of_range));
+    }
+
+    #[test]
+    fn bigger_predators_have_a_larger_capture_range() {
+        let small = capture_range(&Size { value: 0.4 }, 1.5);
+        let big = capture_range(&Size { value: 0.8 }, 1.5);
+        assert!((small - 0.6).abs() < 1e-6);
+        assert!((big - 1.2).abs() < 1e-6);
+        assert!(big > small);
+    }
+}
+
 //#[test]
 //fn test_cooldown_is_reduced() -> Result<(), Error> {
 //AmethystApplication::blank()
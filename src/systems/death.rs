@@ -1,7 +1,13 @@
 use amethyst::{core::transform::Transform, ecs::*, shrev::EventChannel};
 use std::f32;
 
-use crate::components::{combat::Health, creatures::Carcass, digestion::Fullness};
+use crate::components::{
+    combat::Health,
+    creatures::{Carcass, Size},
+    digestion::{Fullness, Nutrition},
+    lifetime::Lifetime,
+};
+use crate::resources::spawn_stats::SpawnStats;
 use crate::systems::spawner::CreatureSpawnEvent;
 
 #[derive(Debug, Clone)]
@@ -17,13 +23,16 @@ impl<'s> System<'s> for StarvationSystem {
         ReadStorage<'s, Fullness>,
         Entities<'s>,
         Write<'s, EventChannel<CreatureDeathEvent>>,
+        Write<'s, SpawnStats>,
     );
 
-    fn run(&mut self, (fullnesses, entities, mut death_events): Self::SystemData) {
+    fn run(&mut self, (fullnesses, entities, mut death_events, mut spawn_stats): Self::SystemData) {
         for (fullness, entity) in (&fullnesses, &*entities).join() {
             if fullness.value < f32::EPSILON {
                 death_events.single_write(CreatureDeathEvent { deceased: entity });
-                let _ = entities.delete(entity);
+                if entities.delete(entity).is_ok() {
+                    spawn_stats.record_despawn();
+                }
             }
         }
     }
@@ -37,13 +46,16 @@ impl<'s> System<'s> for DeathByHealthSystem {
         ReadStorage<'s, Health>,
         Entities<'s>,
         Write<'s, EventChannel<CreatureDeathEvent>>,
+        Write<'s, SpawnStats>,
     );
 
-    fn run(&mut self, (healths, entities, mut death_events): Self::SystemData) {
+    fn run(&mut self, (healths, entities, mut death_events, mut spawn_stats): Self::SystemData) {
         for (health, entity) in (&healths, &*entities).join() {
             if health.value < f32::EPSILON {
                 death_events.single_write(CreatureDeathEvent { deceased: entity });
-                let _ = entities.delete(entity);
+                if entities.delete(entity).is_ok() {
+                    spawn_stats.record_despawn();
+                }
             }
         }
     }
@@ -62,6 +74,7 @@ impl<'s> System<'s> for CarcassSystem {
         Write<'s, LazyUpdate>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, Carcass>,
+        ReadStorage<'s, Size>,
     );
 
     fn setup(&mut self, world: &mut World) {
@@ -75,15 +88,35 @@ impl<'s> System<'s> for CarcassSystem {
 
     fn run(
         &mut self,
-        (entities, death_events, mut spawn_events, lazy_update, transforms, carcasses): Self::SystemData,
+        (
+            entities,
+            death_events,
+            mut spawn_events,
+            lazy_update,
+            transforms,
+            carcasses,
+            sizes,
+        ): Self::SystemData,
     ) {
         for event in death_events.read(self.death_reader_id.as_mut().unwrap()) {
             let mut deceased = BitSet::new();
             deceased.add(event.deceased.id());
 
-            for (_, carcass, transform) in (&deceased, &carcasses, &transforms).join() {
-                let creature_entity_builder =
+            for (_, carcass, transform, size) in
+                (&deceased, &carcasses, &transforms, sizes.maybe()).join()
+            {
+                let mut creature_entity_builder =
                     lazy_update.create_entity(&entities).with(transform.clone());
+                if let Some(energy_per_size) = carcass.energy_per_size {
+                    let size = size.map_or(1.0, |size| size.value);
+                    creature_entity_builder = creature_entity_builder.with(Nutrition {
+                        value: energy_per_size * size,
+                    });
+                }
+                if let Some(decay_time) = carcass.decay_time {
+                    creature_entity_builder =
+                        creature_entity_builder.with(Lifetime::new(decay_time));
+                }
                 spawn_events.single_write(CreatureSpawnEvent {
                     creature_type: carcass.creature_type.clone(),
                     entity: creature_entity_builder.build(),
@@ -92,3 +125,47 @@ impl<'s> System<'s> for CarcassSystem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starvation_leaves_a_carcass_with_energy_from_the_creature_size() {
+        let mut world = World::new();
+        world.register::<Nutrition>();
+        world.register::<Lifetime>();
+        let mut starvation = StarvationSystem;
+        let mut carcass_system = CarcassSystem::default();
+        System::setup(&mut starvation, &mut world);
+        System::setup(&mut carcass_system, &mut world);
+
+        world
+            .create_entity()
+            .with(Fullness {
+                max: 100.0,
+                value: 0.0,
+            })
+            .with(Transform::default())
+            .with(Size { value: 0.8 })
+            .with(Carcass {
+                creature_type: "HerbivoreCarcass".to_string(),
+                energy_per_size: Some(50.0),
+                decay_time: Some(30.0),
+            })
+            .build();
+
+        starvation.run_now(&world);
+        carcass_system.run_now(&world);
+        world.maintain();
+
+        let nutritions = world.read_storage::<Nutrition>();
+        let lifetimes = world.read_storage::<Lifetime>();
+        let carcasses: Vec<_> = (&nutritions, &lifetimes).join().collect();
+        assert_eq!(carcasses.len(), 1);
+        let (nutrition, lifetime) = carcasses[0];
+        assert!((nutrition.value - 40.0).abs() < 1e-4);
+        assert!((lifetime.remaining - 30.0).abs() < 1e-4);
+        assert_eq!(world.read_storage::<Fullness>().join().count(), 0);
+    }
+}
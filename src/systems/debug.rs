@@ -1,9 +1,14 @@
 use amethyst::{
-    ecs::{Entities, Join, ReadStorage, System, WriteStorage},
-    renderer::debug_drawing::DebugLinesComponent,
+    core::math::{Point3, Vector3},
+    core::Transform,
+    ecs::{Entities, Entity, Join, Read, ReadExpect, ReadStorage, System, WriteStorage},
+    renderer::{debug_drawing::DebugLinesComponent, palette::Srgba},
 };
 
-use crate::components::creatures::CreatureTag;
+use crate::{
+    components::creatures::{CreatureTag, Movement},
+    resources::{debug::DebugConfig, physics::PhysicsConfig, world_bounds::WorldBounds},
+};
 
 pub struct DebugSystem;
 impl<'s> System<'s> for DebugSystem {
@@ -26,3 +31,143 @@ impl<'s> System<'s> for DebugSystem {
         }
     }
 }
+
+/// Draws the edges of the WorldBounds as a rectangle at ground height while
+/// DebugConfig::show_bounds is set. Like the other debug drawing systems it runs after the
+/// debug lines are cleared, so it redraws the rectangle every frame.
+#[derive(Default)]
+pub struct BoundsWireframeSystem {
+    wireframe: Option<Entity>,
+}
+
+impl<'s> System<'s> for BoundsWireframeSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, DebugLinesComponent>,
+        ReadExpect<'s, WorldBounds>,
+        Read<'s, PhysicsConfig>,
+        Read<'s, DebugConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, mut debug_lines_comps, bounds, physics_config, debug_config): Self::SystemData,
+    ) {
+        let wireframe = *self.wireframe.get_or_insert_with(|| entities.create());
+        if !debug_lines_comps.contains(wireframe) {
+            debug_lines_comps
+                .insert(wireframe, DebugLinesComponent::with_capacity(4))
+                .expect("Unreachable");
+        }
+        let lines = debug_lines_comps.get_mut(wireframe).expect("Unreachable");
+        lines.clear();
+        if !debug_config.show_bounds {
+            return;
+        }
+        let corners = bounds_corners(&bounds, physics_config.ground_height);
+        for i in 0..corners.len() {
+            lines.add_line(
+                Point3::from(corners[i]),
+                Point3::from(corners[(i + 1) % corners.len()]),
+                Srgba::new(1.0, 1.0, 0.0, 1.0),
+            );
+        }
+    }
+}
+
+/// Draws the velocity of every entity with a Movement as a line from its position while
+/// DebugConfig::show_velocities is set. The length of the line is the speed times
+/// DebugConfig::velocity_line_scale.
+#[derive(Default)]
+pub struct VelocityDebugSystem {
+    lines_entity: Option<Entity>,
+}
+
+impl<'s> System<'s> for VelocityDebugSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, DebugLinesComponent>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Movement>,
+        Read<'s, DebugConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, mut debug_lines_comps, transforms, movements, debug_config): Self::SystemData,
+    ) {
+        let lines_entity = *self.lines_entity.get_or_insert_with(|| entities.create());
+        if !debug_lines_comps.contains(lines_entity) {
+            debug_lines_comps
+                .insert(lines_entity, DebugLinesComponent::new())
+                .expect("Unreachable");
+        }
+        let lines = debug_lines_comps
+            .get_mut(lines_entity)
+            .expect("Unreachable");
+        lines.clear();
+        if !debug_config.show_velocities {
+            return;
+        }
+        for (transform, movement) in (&transforms, &movements).join() {
+            let start = transform.global_matrix().column(3).xyz();
+            lines.add_line(
+                Point3::from(start),
+                Point3::from(velocity_line_end(
+                    start,
+                    movement.velocity,
+                    debug_config.velocity_line_scale,
+                )),
+                Srgba::new(0.0, 1.0, 1.0, 1.0),
+            );
+        }
+    }
+}
+
+/// Returns where a velocity line drawn from the position ends.
+pub fn velocity_line_end(
+    position: Vector3<f32>,
+    velocity: Vector3<f32>,
+    scale: f32,
+) -> Vector3<f32> {
+    position + velocity * scale
+}
+
+/// Returns the corners of the world bounds at the given height, going around counter-clockwise
+/// starting at the bottom left.
+pub fn bounds_corners(bounds: &WorldBounds, height: f32) -> [Vector3<f32>; 4] {
+    [
+        Vector3::new(bounds.left, bounds.bottom, height),
+        Vector3::new(bounds.right, bounds.bottom, height),
+        Vector3::new(bounds.right, bounds.top, height),
+        Vector3::new(bounds.left, bounds.top, height),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corners_lie_on_the_bounds_at_the_given_height() {
+        let corners = bounds_corners(&WorldBounds::new(-4.0, 6.0, -2.0, 3.0), 0.5);
+        assert_eq!(corners[0], Vector3::new(-4.0, -2.0, 0.5));
+        assert_eq!(corners[1], Vector3::new(6.0, -2.0, 0.5));
+        assert_eq!(corners[2], Vector3::new(6.0, 3.0, 0.5));
+        assert_eq!(corners[3], Vector3::new(-4.0, 3.0, 0.5));
+    }
+
+    #[test]
+    fn velocity_line_is_scaled_by_speed() {
+        let position = Vector3::new(1.0, -2.0, 0.5);
+        assert_eq!(
+            velocity_line_end(position, Vector3::new(2.0, 0.0, -1.0), 0.5),
+            Vector3::new(2.0, -2.0, 0.0)
+        );
+        assert_eq!(
+            velocity_line_end(position, Vector3::new(0.0, 4.0, 0.0), 0.25),
+            Vector3::new(1.0, -1.0, 0.5)
+        );
+        assert_eq!(velocity_line_end(position, Vector3::zeros(), 0.5), position);
+    }
+}
@@ -0,0 +1,118 @@
+use amethyst::{
+    ecs::*,
+    input::{InputEvent, StringBindings},
+    shrev::{EventChannel, ReaderId},
+};
+use serde::{Deserialize, Serialize};
+
+/// Commands that control the simulation for debugging purposes. Systems and states consume these
+/// from an `EventChannel<DebugCommand>` instead of parsing raw input events themselves, which also
+/// allows commands to be issued from code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DebugCommand {
+    ToggleDebug,
+    TogglePause,
+    SpeedUp,
+    SlowDown,
+    RotateWindCounterClockwise,
+    RotateWindClockwise,
+    ToggleWindLogging,
+    ToggleGravity,
+    ToggleBoundsWireframe,
+    ToggleSandbox,
+    SpawnTopplegrass,
+    SpawnTopplegrassAtEdge,
+    PickEntity,
+    TogglePinned,
+    ResetSim,
+    ToggleVelocities,
+    PlaceFan,
+    ToggleSpawning,
+}
+
+impl DebugCommand {
+    /// Returns the command bound to the given input action, if any. The action names need to be
+    /// kept in alignment with input.ron.
+    pub fn from_action(action: &str) -> Option<DebugCommand> {
+        match action {
+            "ToggleDebug" => Some(DebugCommand::ToggleDebug),
+            "TogglePause" => Some(DebugCommand::TogglePause),
+            "SpeedUp" => Some(DebugCommand::SpeedUp),
+            "SlowDown" => Some(DebugCommand::SlowDown),
+            "ChangeWindDirectionCCW" => Some(DebugCommand::RotateWindCounterClockwise),
+            "ChangeWindDirectionCW" => Some(DebugCommand::RotateWindClockwise),
+            "ToggleWindLogging" => Some(DebugCommand::ToggleWindLogging),
+            "ToggleGravity" => Some(DebugCommand::ToggleGravity),
+            "ToggleBoundsWireframe" => Some(DebugCommand::ToggleBoundsWireframe),
+            "ToggleSandbox" => Some(DebugCommand::ToggleSandbox),
+            "SpawnTopplegrass" => Some(DebugCommand::SpawnTopplegrass),
+            "SpawnTopplegrassAtEdge" => Some(DebugCommand::SpawnTopplegrassAtEdge),
+            "PickEntity" => Some(DebugCommand::PickEntity),
+            "TogglePinned" => Some(DebugCommand::TogglePinned),
+            "ResetSim" => Some(DebugCommand::ResetSim),
+            "ToggleVelocities" => Some(DebugCommand::ToggleVelocities),
+            "PlaceFan" => Some(DebugCommand::PlaceFan),
+            "ToggleSpawning" => Some(DebugCommand::ToggleSpawning),
+            _ => None,
+        }
+    }
+}
+
+/// Translates input actions into DebugCommands.
+#[derive(Default)]
+pub struct DebugInputSystem {
+    input_reader_id: Option<ReaderId<InputEvent<StringBindings>>>,
+}
+
+impl<'s> System<'s> for DebugInputSystem {
+    type SystemData = (
+        Read<'s, EventChannel<InputEvent<StringBindings>>>,
+        Write<'s, EventChannel<DebugCommand>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.input_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (input_events, mut debug_commands): Self::SystemData) {
+        for event in input_events.read(self.input_reader_id.as_mut().unwrap()) {
+            if let InputEvent::ActionPressed(action) = event {
+                if let Some(command) = DebugCommand::from_action(action) {
+                    debug_commands.single_write(command);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_pause_action_produces_toggle_pause_command() {
+        let mut world = World::new();
+        let mut system = DebugInputSystem::default();
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<DebugCommand>>()
+            .register_reader();
+
+        world
+            .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+            .single_write(InputEvent::ActionPressed("TogglePause".to_string()));
+        world
+            .fetch_mut::<EventChannel<InputEvent<StringBindings>>>()
+            .single_write(InputEvent::ActionPressed("CameraMoveUp".to_string()));
+        system.run_now(&world);
+
+        let channel = world.fetch::<EventChannel<DebugCommand>>();
+        let commands: Vec<DebugCommand> = channel.read(&mut reader).cloned().collect();
+        assert_eq!(commands, vec![DebugCommand::TogglePause]);
+    }
+}
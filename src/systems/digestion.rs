@@ -1,7 +1,8 @@
-use amethyst::renderer::{debug_drawing::DebugLines, palette::Srgba};
-use amethyst::{core::Time, core::Transform, ecs::*};
+use amethyst::renderer::{debug_drawing::DebugLines, palette::Srgba, resources::Tint};
+use amethyst::{core::Named, core::Time, core::Transform, ecs::*};
 
 use crate::components::digestion::{Digestion, Fullness};
+use crate::resources::species_color::SpeciesColor;
 
 pub struct DigestionSystem;
 
@@ -42,3 +43,65 @@ impl<'s> System<'s> for DebugFullnessSystem {
         }
     }
 }
+
+/// Tints creatures toward red as they starve. Well fed creatures have the color of their species
+/// in the SpeciesColor resource, or green if their species has no color.
+/// Only entities that already have a Tint are affected.
+pub struct TintByEnergySystem;
+
+impl<'s> System<'s> for TintByEnergySystem {
+    type SystemData = (
+        ReadStorage<'s, Fullness>,
+        ReadStorage<'s, Named>,
+        WriteStorage<'s, Tint>,
+        Read<'s, SpeciesColor>,
+    );
+
+    fn run(&mut self, (fullnesses, names, mut tints, species_color): Self::SystemData) {
+        for (fullness, name, tint) in (&fullnesses, names.maybe(), &mut tints).join() {
+            let full_color = name
+                .and_then(|name| species_color.color(&name.name))
+                .unwrap_or_else(|| Srgba::new(0.0, 1.0, 0.0, 1.0));
+            tint.0 = energy_tint_towards(fullness, full_color);
+        }
+    }
+}
+
+/// Maps the fullness ratio to a color: green when full, red when starving.
+pub fn energy_tint(fullness: &Fullness) -> Srgba {
+    energy_tint_towards(fullness, Srgba::new(0.0, 1.0, 0.0, 1.0))
+}
+
+/// Maps the fullness ratio to a color: full_color when full, red when starving.
+pub fn energy_tint_towards(fullness: &Fullness, full_color: Srgba) -> Srgba {
+    let ratio = if fullness.max > 0.0 {
+        (fullness.value / fullness.max).max(0.0).min(1.0)
+    } else {
+        1.0
+    };
+    Srgba::new(
+        (1.0 - ratio) + full_color.red * ratio,
+        full_color.green * ratio,
+        full_color.blue * ratio,
+        full_color.alpha,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fullness(value: f32) -> Fullness {
+        Fullness { max: 100.0, value }
+    }
+
+    #[test]
+    fn energy_maps_from_red_to_green() {
+        assert_eq!(energy_tint(&fullness(0.0)), Srgba::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(energy_tint(&fullness(50.0)), Srgba::new(0.5, 0.5, 0.0, 1.0));
+        assert_eq!(
+            energy_tint(&fullness(100.0)),
+            Srgba::new(0.0, 1.0, 0.0, 1.0)
+        );
+    }
+}
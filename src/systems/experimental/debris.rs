@@ -0,0 +1,240 @@
+use amethyst::{
+    core::{
+        math::{Vector2, Vector3},
+        timing::Time,
+        transform::Transform,
+    },
+    ecs::*,
+    shrev::EventChannel,
+};
+
+use rand::Rng;
+
+use crate::{
+    components::{
+        creatures::{Movement, TopplegrassTag},
+        lifetime::Lifetime,
+    },
+    resources::{
+        game_rng::GameRng, topplegrass::TopplegrassConfig, wind::Wind, world_bounds::WorldBounds,
+    },
+    systems::spawner::CreatureSpawnEvent,
+};
+
+/// How long a piece of debris lives, in seconds.
+const DEBRIS_LIFETIME: f32 = 1.0;
+/// The scaling to apply to a piece of debris.
+const DEBRIS_SCALE: f32 = 0.0005;
+/// How far behind the topplegrass the debris appears.
+const DEBRIS_OFFSET: f32 = 0.3;
+/// The speed with which debris is kicked away from the topplegrass.
+const DEBRIS_SPEED: f32 = 0.3;
+/// How long a piece of debris blown in by the wind lives, in seconds.
+const AMBIENT_DEBRIS_LIFETIME: f32 = 4.0;
+/// At which height debris blown in by the wind floats.
+const AMBIENT_DEBRIS_HEIGHT: f32 = 0.3;
+
+/// Makes topplegrass that is rolling fast kick up little bits of debris behind it.
+/// The faster the topplegrass, the more debris. Debris is cleaned up by the LifetimeSystem.
+/// Only active when TopplegrassConfig::debris_enabled is set.
+#[derive(Default)]
+pub struct DebrisEmitterSystem;
+
+impl<'s> System<'s> for DebrisEmitterSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Movement>,
+        ReadStorage<'s, TopplegrassTag>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        Read<'s, Time>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            transforms,
+            movements,
+            topple_tags,
+            lazy_update,
+            mut spawn_events,
+            time,
+            config,
+            mut rng,
+        ): Self::SystemData,
+    ) {
+        if !config.debris_enabled {
+            return;
+        }
+        for (transform, movement, _) in (&transforms, &movements, &topple_tags).join() {
+            let velocity = Vector2::new(movement.velocity.x, movement.velocity.y);
+            let rate = debris_rate(velocity.magnitude(), &config);
+            if rate <= 0.0 || rng.gen::<f32>() >= rate * time.delta_seconds() {
+                continue;
+            }
+            let behind = -velocity.normalize();
+            let mut debris_transform = Transform::default();
+            debris_transform.set_scale(Vector3::new(DEBRIS_SCALE, DEBRIS_SCALE, DEBRIS_SCALE));
+            debris_transform.set_translation(
+                transform.translation() + Vector3::new(behind.x, behind.y, 0.0) * DEBRIS_OFFSET,
+            );
+            let entity = lazy_update
+                .create_entity(&entities)
+                .with(debris_transform)
+                .with(Movement {
+                    velocity: Vector3::new(behind.x, behind.y, 0.0) * DEBRIS_SPEED,
+                    max_movement_speed: DEBRIS_SPEED,
+                })
+                .with(Lifetime::new(DEBRIS_LIFETIME))
+                .build();
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Debris".to_string(),
+                entity,
+            });
+        }
+    }
+}
+
+/// Periodically spawns debris, like leaves and dust, at random positions in the world that drifts
+/// along with the wind. The stronger the wind, the more debris:
+/// TopplegrassConfig::ambient_debris_rate pieces per second, per unit of wind speed.
+/// Debris is cleaned up by the LifetimeSystem.
+#[derive(Default)]
+pub struct DebrisSpawnSystem {
+    /// Fractional amount of debris that is due but hasn't been spawned yet.
+    pending: f32,
+}
+
+impl<'s> System<'s> for DebrisSpawnSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        Read<'s, Time>,
+        Read<'s, Wind>,
+        ReadExpect<'s, WorldBounds>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, lazy_update, mut spawn_events, time, wind, bounds, config, mut rng): Self::SystemData,
+    ) {
+        self.pending += config.ambient_debris_rate * wind.wind.magnitude() * time.delta_seconds();
+        while self.pending >= 1.0 {
+            self.pending -= 1.0;
+            let mut transform = Transform::default();
+            transform.set_scale(Vector3::new(DEBRIS_SCALE, DEBRIS_SCALE, DEBRIS_SCALE));
+            transform.set_translation_xyz(
+                rng.gen_range(bounds.left, bounds.right),
+                rng.gen_range(bounds.bottom, bounds.top),
+                AMBIENT_DEBRIS_HEIGHT,
+            );
+            let entity = lazy_update
+                .create_entity(&entities)
+                .with(transform)
+                .with(Movement {
+                    velocity: Vector3::new(wind.wind.x, wind.wind.y, 0.0),
+                    max_movement_speed: wind.wind.magnitude(),
+                })
+                .with(Lifetime::new(AMBIENT_DEBRIS_LIFETIME))
+                .build();
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Debris".to_string(),
+                entity,
+            });
+        }
+    }
+}
+
+/// Returns how many pieces of debris per second a topplegrass moving at the given speed emits.
+fn debris_rate(speed: f32, config: &TopplegrassConfig) -> f32 {
+    if speed > config.debris_speed_threshold {
+        speed * config.debris_rate
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitted_debris(speed: f32) -> usize {
+        let mut world = World::new();
+        let mut system = DebrisEmitterSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(TopplegrassConfig {
+            debris_enabled: true,
+            ..Default::default()
+        });
+        // Large enough that a fast topplegrass is guaranteed to emit this frame.
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+        world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::new(speed, 0.0, 0.0),
+                max_movement_speed: 100.0,
+            })
+            .with(TopplegrassTag)
+            .build();
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        system.run_now(&world);
+
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        let count = spawn_events.read(&mut reader).count();
+        count
+    }
+
+    #[test]
+    fn fast_topplegrass_emits_debris() {
+        assert_eq!(emitted_debris(10.0), 1);
+    }
+
+    #[test]
+    fn slow_topplegrass_does_not_emit_debris() {
+        assert_eq!(emitted_debris(0.5), 0);
+    }
+
+    fn blown_in_debris(wind: Wind) -> World {
+        let mut world = World::new();
+        let mut system = DebrisSpawnSystem::default();
+        System::setup(&mut system, &mut world);
+        world.register::<Transform>();
+        world.register::<Movement>();
+        world.register::<Lifetime>();
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        world.insert(TopplegrassConfig {
+            ambient_debris_rate: 1.0,
+            ..Default::default()
+        });
+        world.insert(wind);
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        for _ in 0..10 {
+            system.run_now(&world);
+        }
+        world.maintain();
+        world
+    }
+
+    #[test]
+    fn stronger_wind_blows_in_more_debris() {
+        let calm = blown_in_debris(Wind::new(0.5, 0.0));
+        let windy = blown_in_debris(Wind::new(0.0, 4.0));
+        let calm_count = calm.read_storage::<Lifetime>().join().count();
+        let windy_count = windy.read_storage::<Lifetime>().join().count();
+        assert!(windy_count > calm_count);
+        for lifetime in windy.read_storage::<Lifetime>().join() {
+            assert!(lifetime.remaining.is_finite() && lifetime.remaining > 0.0);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use amethyst::{
+    core::{math::Vector3, transform::Transform, Hidden},
+    ecs::*,
+    renderer::camera::Camera,
+};
+
+use crate::{components::creatures::TopplegrassTag, resources::topplegrass::TopplegrassConfig};
+
+/// Hides topplegrass that is too far away from the camera to be worth rendering, and shows it
+/// again once the camera gets close. Only rendering is affected; all other systems keep running.
+#[derive(Default)]
+pub struct DistanceCullSystem;
+
+impl<'s> System<'s> for DistanceCullSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, TopplegrassTag>,
+        WriteStorage<'s, Hidden>,
+        Read<'s, TopplegrassConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, cameras, transforms, topplegrass_tags, mut hiddens, config): Self::SystemData,
+    ) {
+        let camera_position = match (&cameras, &transforms).join().next() {
+            Some((_, transform)) => *transform.translation(),
+            None => return,
+        };
+        for (entity, transform, _) in (&entities, &transforms, &topplegrass_tags).join() {
+            if in_range(
+                &camera_position,
+                transform.translation(),
+                config.cull_distance,
+            ) {
+                hiddens.remove(entity);
+            } else if !hiddens.contains(entity) {
+                hiddens
+                    .insert(entity, Hidden)
+                    .expect("Unreachable: we are inserting now.");
+            }
+        }
+    }
+}
+
+/// Whether a position is close enough to the camera to be rendered.
+fn in_range(camera_position: &Vector3<f32>, position: &Vector3<f32>, distance: f32) -> bool {
+    (position - camera_position).magnitude_squared() <= distance * distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions_are_classified_by_distance_to_the_camera() {
+        let camera_position = Vector3::new(-10.0, -10.0, 8.0);
+        assert!(in_range(
+            &camera_position,
+            &Vector3::new(-10.0, -10.0, 0.0),
+            10.0
+        ));
+        assert!(in_range(
+            &camera_position,
+            &Vector3::new(-4.0, -10.0, 0.0),
+            10.0
+        ));
+        assert!(!in_range(
+            &camera_position,
+            &Vector3::new(0.0, -10.0, 0.0),
+            10.0
+        ));
+        assert!(!in_range(
+            &camera_position,
+            &Vector3::new(10.0, 10.0, 0.5),
+            10.0
+        ));
+    }
+}
@@ -0,0 +1,218 @@
+use amethyst::{
+    core::{
+        math::{Point2, Vector2},
+        timing::Time,
+        transform::Transform,
+    },
+    ecs::*,
+    input::{InputHandler, StringBindings},
+    renderer::camera::Camera,
+    shrev::{EventChannel, ReaderId},
+    window::ScreenDimensions,
+};
+
+use crate::{
+    components::{
+        creatures::{DragProfile, Movement, RestingTag},
+        fan::Fan,
+    },
+    systems::debug_input::DebugCommand,
+};
+
+/// Acceleration of a fan placed through the PlaceFan debug command.
+const PLACED_FAN_STRENGTH: f32 = 4.0;
+/// Radius of a fan placed through the PlaceFan debug command.
+const PLACED_FAN_RADIUS: f32 = 3.0;
+
+/// Lets every Fan push the entities with a DragProfile within its radius in its direction. The
+/// push is strongest right at the fan and fades out towards the edge of its radius. It is added on
+/// top of the drag of the global wind, so it should run after the WindForceSystem.
+#[derive(Default)]
+pub struct FanSystem;
+
+impl<'s> System<'s> for FanSystem {
+    type SystemData = (
+        ReadStorage<'s, Fan>,
+        WriteStorage<'s, Movement>,
+        ReadStorage<'s, DragProfile>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, RestingTag>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (fans, mut movements, drag_profiles, transforms, resting_tags, time): Self::SystemData,
+    ) {
+        for fan in fans.join() {
+            if fan.radius <= 0.0 || fan.direction.magnitude() <= std::f32::EPSILON {
+                continue;
+            }
+            let direction = fan.direction.normalize();
+            for (movement, transform, _, _) in
+                (&mut movements, &transforms, &drag_profiles, !&resting_tags).join()
+            {
+                let velocity = fan_push(
+                    fan,
+                    direction,
+                    transform.translation().xy(),
+                    time.delta_seconds(),
+                );
+                movement.velocity.x += velocity.x;
+                movement.velocity.y += velocity.y;
+            }
+        }
+    }
+}
+
+/// Returns the velocity the fan adds to an entity at the given position over delta_seconds.
+fn fan_push(
+    fan: &Fan,
+    direction: Vector2<f32>,
+    position: Vector2<f32>,
+    delta_seconds: f32,
+) -> Vector2<f32> {
+    let distance = (position - fan.position).magnitude();
+    if distance >= fan.radius {
+        return Vector2::new(0.0, 0.0);
+    }
+    direction * fan.strength * (1.0 - distance / fan.radius) * delta_seconds
+}
+
+/// Places a Fan on the ground under the cursor on a PlaceFan debug command. It blows towards the
+/// center of the world, so it can be used to keep the topplegrass in view.
+#[derive(Default)]
+pub struct FanPlacementSystem {
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+}
+
+impl<'s> System<'s> for FanPlacementSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Fan>,
+        Read<'s, InputHandler<StringBindings>>,
+        ReadExpect<'s, ScreenDimensions>,
+        Read<'s, EventChannel<DebugCommand>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            cameras,
+            transforms,
+            mut fans,
+            input_handler,
+            screen_dimensions,
+            debug_commands,
+        ): Self::SystemData,
+    ) {
+        let place_requested = debug_commands
+            .read(self.command_reader_id.as_mut().unwrap())
+            .any(|command| *command == DebugCommand::PlaceFan);
+        if !place_requested {
+            return;
+        }
+        let (mouse_x, mouse_y) = match input_handler.mouse_position() {
+            Some(position) => position,
+            None => return,
+        };
+        let (camera, camera_transform) = match (&cameras, &transforms).join().next() {
+            Some(camera) => camera,
+            None => return,
+        };
+        let ray = camera.projection().screen_ray(
+            Point2::new(mouse_x, mouse_y),
+            Vector2::new(screen_dimensions.width(), screen_dimensions.height()),
+            camera_transform,
+        );
+        if ray.direction.z.abs() <= std::f32::EPSILON {
+            return;
+        }
+        // Where the ray hits the ground at z = 0.
+        let distance = -ray.origin.z / ray.direction.z;
+        if distance < 0.0 {
+            return;
+        }
+        let position = (ray.origin.coords + ray.direction * distance).xy();
+        let direction = if position.magnitude() > std::f32::EPSILON {
+            -position.normalize()
+        } else {
+            Vector2::new(1.0, 0.0)
+        };
+        let fan = entities.create();
+        fans.insert(
+            fan,
+            Fan {
+                position,
+                direction,
+                strength: PLACED_FAN_STRENGTH,
+                radius: PLACED_FAN_RADIUS,
+            },
+        )
+        .expect("Unable to place fan");
+        info!(
+            "Placed a fan at {:?}, blowing towards {:?}",
+            position, direction
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn entity_within_the_radius_is_pushed_in_the_fan_direction() {
+        let mut world = World::new();
+        let mut system = FanSystem;
+        System::setup(&mut system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        world
+            .create_entity()
+            .with(Fan {
+                position: Vector2::new(0.0, 0.0),
+                direction: Vector2::new(0.0, 2.0),
+                strength: 4.0,
+                radius: 3.0,
+            })
+            .build();
+        let mut spawn = |x: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, 0.0, 0.5);
+            world
+                .create_entity()
+                .with(transform)
+                .with(Movement {
+                    velocity: Vector3::new(1.0, 0.0, 0.0),
+                    max_movement_speed: 10.0,
+                })
+                .with(DragProfile { area: 1.0 })
+                .build()
+        };
+        let near = spawn(1.5);
+        let far = spawn(5.0);
+
+        system.run_now(&world);
+
+        let movements = world.read_storage::<Movement>();
+        let near_velocity = movements.get(near).unwrap().velocity;
+        assert!((near_velocity.x - 1.0).abs() < 1e-6);
+        // Halfway to the edge of the radius, the fan pushes at half its strength.
+        assert!((near_velocity.y - 1.0).abs() < 1e-6);
+        let far_velocity = movements.get(far).unwrap().velocity;
+        assert_eq!(far_velocity, Vector3::new(1.0, 0.0, 0.0));
+    }
+}
@@ -1,26 +1,444 @@
-use amethyst::{core::timing::Time, ecs::*};
+use amethyst::{
+    core::{math::Vector2, timing::Time, transform::Transform},
+    ecs::*,
+};
 
-use crate::{components::creatures::FallingTag, components::creatures::Movement};
+use crate::{
+    components::creatures::FallingTag,
+    components::creatures::Movement,
+    components::pinned::Pinned,
+    resources::physics::{Physics, PhysicsConfig},
+    resources::two_dimensional_mode::TwoDimensionalMode,
+    resources::world_bounds::WorldBounds,
+};
 
 /// Acceleration due to gravity.
-const GRAVITY: f32 = 4.0;
+pub const GRAVITY: f32 = 4.0;
 
 /// Applies the force of gravity on all entities with the FallingTag.
-/// Will remove the tag if an entity has reached the ground again.
+/// Each frame is divided into PhysicsConfig::gravity_substeps integration steps, or more if needed
+/// to keep each step below PhysicsConfig::max_substep_seconds. If the entity
+/// would pass through the ground during one of those steps, or come within
+/// PhysicsConfig::ground_tolerance of it, its vertical velocity is adjusted so that it ends up
+/// exactly on the ground at the end of this frame instead. An entity that already ended up below
+/// the ground is lifted back onto it.
+/// Air resistance slows the fall, less so higher up where the air is thinner, as configured in
+/// the PhysicsConfig. Close to the ground, gravity can be weakened to cushion the landing.
+/// Does nothing while the TwoDimensionalMode resource is enabled, or while gravity is disabled in
+/// the Physics resource. Pinned entities are left alone.
 #[derive(Default)]
 pub struct GravitySystem;
 
 impl<'s> System<'s> for GravitySystem {
     type SystemData = (
         WriteStorage<'s, Movement>,
+        ReadStorage<'s, Transform>,
         ReadStorage<'s, FallingTag>,
+        ReadStorage<'s, Pinned>,
         Read<'s, Time>,
+        Read<'s, TwoDimensionalMode>,
+        Read<'s, PhysicsConfig>,
+        Read<'s, Physics>,
+        Read<'s, WorldBounds>,
     );
 
-    fn run(&mut self, (mut movements, falling_tags, time): Self::SystemData) {
-        for (movement, _) in (&mut movements, &falling_tags).join() {
+    fn run(
+        &mut self,
+        (
+            mut movements,
+            transforms,
+            falling_tags,
+            pinned,
+            time,
+            two_dimensional_mode,
+            config,
+            physics,
+            bounds,
+        ): Self::SystemData,
+    ) {
+        let delta_seconds = time.delta_seconds();
+        if two_dimensional_mode.0 || !physics.gravity_enabled || delta_seconds <= f32::EPSILON {
+            return;
+        }
+        let substeps = substep_count(delta_seconds, &config);
+        let step = delta_seconds / substeps as f32;
+        for (movement, transform, _, _) in
+            (&mut movements, &transforms, &falling_tags, !&pinned).join()
+        {
             //TODO: Add terminal velocity cap on falling speed.
-            movement.velocity.z -= GRAVITY * time.delta_seconds();
+            let start_z = transform.translation().z;
+            let mut z = start_z;
+            let mut velocity_z = movement.velocity.z;
+            let mut landed = false;
+            for _ in 0..substeps {
+                velocity_z -= gravity_at(z, &config) * step;
+                velocity_z -= velocity_z * (air_drag(z, &bounds, &config) * step).min(1.0);
+                z += velocity_z * step;
+                if z <= config.ground_height + config.ground_tolerance
+                    && velocity_z.is_sign_negative()
+                {
+                    landed = true;
+                    break;
+                }
+            }
+            movement.velocity.z = if landed {
+                (config.ground_height - start_z) / delta_seconds
+            } else {
+                velocity_z
+            };
+        }
+    }
+}
+
+/// Returns the number of integration steps to divide a frame of delta_seconds into.
+fn substep_count(delta_seconds: f32, config: &PhysicsConfig) -> u32 {
+    let needed = if config.max_substep_seconds > f32::EPSILON {
+        (delta_seconds / config.max_substep_seconds).ceil() as u32
+    } else {
+        1
+    };
+    needed.max(config.gravity_substeps).max(1)
+}
+
+/// Returns the acceleration due to gravity at height z. Within PhysicsConfig::landing_cushion_height
+/// of the ground, it ramps down linearly from GRAVITY to PhysicsConfig::landing_cushion_gravity
+/// times GRAVITY at the ground.
+pub fn gravity_at(z: f32, config: &PhysicsConfig) -> f32 {
+    let height = z - config.ground_height;
+    if config.landing_cushion_height <= f32::EPSILON || height >= config.landing_cushion_height {
+        return GRAVITY;
+    }
+    let depth = height.max(0.0) / config.landing_cushion_height;
+    GRAVITY * (config.landing_cushion_gravity + (1.0 - config.landing_cushion_gravity) * depth)
+}
+
+/// Returns the fraction of its vertical speed an entity at height z loses per second to air
+/// resistance. The air thins out between the ground and WorldBounds::ceiling, following the
+/// PhysicsConfig::air_density_exponent curve.
+pub fn air_drag(z: f32, bounds: &WorldBounds, config: &PhysicsConfig) -> f32 {
+    let span = bounds.ceiling - config.ground_height;
+    let altitude = if span > f32::EPSILON {
+        ((z - config.ground_height) / span).max(0.0).min(1.0)
+    } else {
+        0.0
+    };
+    let density =
+        1.0 - (1.0 - config.ceiling_air_density) * altitude.powf(config.air_density_exponent);
+    config.air_drag * density
+}
+
+/// Predicts where and when a falling entity will land, assuming it is only affected by gravity
+/// from now on. Returns the predicted horizontal landing position and the time in seconds until
+/// the landing. An entity that is already on (or below) the ground lands right where it is.
+/// Without gravity, an entity that isn't moving down never lands; its time is infinite.
+pub fn predict_landing(
+    transform: &Transform,
+    movement: &Movement,
+    gravity: f32,
+    ground_height: f32,
+) -> (Vector2<f32>, f32) {
+    let position = transform.translation();
+    let start = Vector2::new(position.x, position.y);
+    let velocity = Vector2::new(movement.velocity.x, movement.velocity.y);
+    let height = position.z - ground_height;
+    let velocity_z = movement.velocity.z;
+    if height <= 0.0 && velocity_z <= 0.0 {
+        return (start, 0.0);
+    }
+    let time = if gravity > f32::EPSILON {
+        // Solve height + velocity_z * t - gravity / 2 * t^2 = 0 for the positive root.
+        (velocity_z + (velocity_z * velocity_z + 2.0 * gravity * height).sqrt()) / gravity
+    } else if velocity_z < 0.0 {
+        -height / velocity_z
+    } else {
+        return (start, f32::INFINITY);
+    };
+    (start + velocity * time, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::movement::MovementSystem;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn large_delta_does_not_overshoot_the_ground() {
+        let mut world = World::new();
+        let mut gravity = GravitySystem::default();
+        let mut movement = MovementSystem;
+        System::setup(&mut gravity, &mut world);
+        System::setup(&mut movement, &mut world);
+        world.insert(PhysicsConfig {
+            ground_height: 0.5,
+            gravity_substeps: 8,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(0.0, 0.0, 0.6);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(0.0, 0.0, -10.0),
+                max_movement_speed: 100.0,
+            })
+            .with(FallingTag)
+            .build();
+
+        gravity.run_now(&world);
+        movement.run_now(&world);
+
+        let transforms = world.read_storage::<Transform>();
+        let z = transforms.get(entity).unwrap().translation().z;
+        assert!((z - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn landing_cushion_lowers_the_impact_velocity() {
+        let impact_speed = |config: PhysicsConfig| {
+            let mut world = World::new();
+            let mut gravity = GravitySystem::default();
+            let mut movement = MovementSystem;
+            System::setup(&mut gravity, &mut world);
+            System::setup(&mut movement, &mut world);
+            world.insert(config);
+            world.write_resource::<Time>().set_delta_seconds(1.0 / 60.0);
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(0.0, 0.0, 3.0);
+            let entity = world
+                .create_entity()
+                .with(transform)
+                .with(Movement {
+                    velocity: Vector3::zeros(),
+                    max_movement_speed: 100.0,
+                })
+                .with(FallingTag)
+                .build();
+            let mut fastest: f32 = 0.0;
+            for _ in 0..600 {
+                gravity.run_now(&world);
+                movement.run_now(&world);
+                let velocity_z = world
+                    .read_storage::<Movement>()
+                    .get(entity)
+                    .unwrap()
+                    .velocity
+                    .z;
+                fastest = fastest.max(-velocity_z);
+                let z = world
+                    .read_storage::<Transform>()
+                    .get(entity)
+                    .unwrap()
+                    .translation()
+                    .z;
+                if z <= 0.5 + 1e-4 {
+                    break;
+                }
+            }
+            fastest
+        };
+        let hard = impact_speed(PhysicsConfig::default());
+        let cushioned = impact_speed(PhysicsConfig {
+            landing_cushion_height: 1.5,
+            landing_cushion_gravity: 0.2,
+            ..Default::default()
+        });
+        // Falling 2.5 units at full gravity ends at about sqrt(2 * 4 * 2.5) = 4.5 units per second.
+        assert!(hard > 4.0);
+        assert!(cushioned < hard - 0.3);
+    }
+
+    #[test]
+    fn high_entities_experience_less_drag() {
+        let config = PhysicsConfig {
+            air_drag: 1.0,
+            ..Default::default()
+        };
+        let bounds = WorldBounds::new(-10.0, 10.0, -10.0, 10.0);
+        let near_ground = air_drag(1.0, &bounds, &config);
+        let high_up = air_drag(9.0, &bounds, &config);
+        assert!(high_up < near_ground);
+        assert!((air_drag(bounds.ceiling, &bounds, &config) - 0.3).abs() < 1e-5);
+
+        let mut world = World::new();
+        let mut gravity = GravitySystem::default();
+        System::setup(&mut gravity, &mut world);
+        world.insert(config);
+        world.insert(bounds);
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+        let mut falling_from = |z: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(0.0, 0.0, z);
+            world
+                .create_entity()
+                .with(transform)
+                .with(Movement {
+                    velocity: Vector3::new(0.0, 0.0, -2.0),
+                    max_movement_speed: 100.0,
+                })
+                .with(FallingTag)
+                .build()
+        };
+        let low = falling_from(2.0);
+        let high = falling_from(9.0);
+
+        gravity.run_now(&world);
+
+        let movements = world.read_storage::<Movement>();
+        let low_speed = movements.get(low).unwrap().velocity.z;
+        let high_speed = movements.get(high).unwrap().velocity.z;
+        assert!(high_speed < low_speed);
+    }
+
+    #[test]
+    fn entity_below_or_just_above_the_ground_snaps_onto_it() {
+        let mut world = World::new();
+        let mut gravity = GravitySystem::default();
+        let mut movement = MovementSystem;
+        System::setup(&mut gravity, &mut world);
+        System::setup(&mut movement, &mut world);
+        world.insert(PhysicsConfig {
+            ground_height: 0.5,
+            gravity_substeps: 1,
+            ground_tolerance: 0.01,
+            ..Default::default()
+        });
+        // A short frame, so falling entities don't reach the ground within it by themselves.
+        world.write_resource::<Time>().set_delta_seconds(0.001);
+
+        let mut falling = |z: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(0.0, 0.0, z);
+            world
+                .create_entity()
+                .with(transform)
+                .with(Movement {
+                    velocity: Vector3::new(0.0, 0.0, -0.001),
+                    max_movement_speed: 100.0,
+                })
+                .with(FallingTag)
+                .build()
+        };
+        // Overshot below the ground on an earlier frame.
+        let below = falling(0.45);
+        // Hovering within the tolerance, falling too slowly to reach the ground this frame.
+        let hovering = falling(0.505);
+
+        gravity.run_now(&world);
+        movement.run_now(&world);
+
+        let transforms = world.read_storage::<Transform>();
+        for entity in [below, hovering].iter() {
+            let z = transforms.get(*entity).unwrap().translation().z;
+            assert!((z - 0.5).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn disabled_gravity_leaves_vertical_velocity_alone() {
+        let mut world = World::new();
+        let mut gravity = GravitySystem::default();
+        System::setup(&mut gravity, &mut world);
+        world.insert(Physics {
+            gravity_enabled: false,
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(0.0, 0.0, 3.0);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(1.0, 0.0, 0.25),
+                max_movement_speed: 100.0,
+            })
+            .with(FallingTag)
+            .build();
+
+        for _ in 0..5 {
+            gravity.run_now(&world);
         }
+
+        let movements = world.read_storage::<Movement>();
+        assert_eq!(movements.get(entity).unwrap().velocity.z, 0.25);
+    }
+
+    #[test]
+    fn predicts_landing_of_a_projectile() {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(1.0, 2.0, 0.5);
+        let movement = Movement {
+            velocity: Vector3::new(3.0, -1.0, 2.0),
+            max_movement_speed: 100.0,
+        };
+        // Thrown up at 2 units per second with a gravity of 4, it takes 0.5 seconds to reach the
+        // top of its arc and another 0.5 seconds to come back down to its starting height.
+        let (landing, time) = predict_landing(&transform, &movement, 4.0, 0.5);
+        assert!((time - 1.0).abs() < 1e-6);
+        assert!((landing - Vector2::new(4.0, 1.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn grounded_entity_lands_immediately() {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(1.0, 2.0, 0.5);
+        let movement = Movement {
+            velocity: Vector3::new(3.0, 0.0, 0.0),
+            max_movement_speed: 100.0,
+        };
+        let (landing, time) = predict_landing(&transform, &movement, GRAVITY, 0.5);
+        assert_eq!(time, 0.0);
+        assert_eq!(landing, Vector2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn very_large_delta_lands_on_the_ground_instead_of_passing_through() {
+        let mut world = World::new();
+        let mut gravity = GravitySystem::default();
+        let mut movement = MovementSystem;
+        System::setup(&mut gravity, &mut world);
+        System::setup(&mut movement, &mut world);
+        world.insert(PhysicsConfig {
+            ground_height: 0.5,
+            gravity_substeps: 1,
+            max_substep_seconds: 0.01,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(5.0);
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(0.0, 0.0, 1.0);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(0.0, 0.0, 3.0),
+                max_movement_speed: 100.0,
+            })
+            .with(FallingTag)
+            .build();
+
+        gravity.run_now(&world);
+        movement.run_now(&world);
+
+        let transforms = world.read_storage::<Transform>();
+        let z = transforms.get(entity).unwrap().translation().z;
+        assert!((z - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn slow_frames_take_extra_substeps() {
+        let config = PhysicsConfig {
+            ground_height: 0.5,
+            gravity_substeps: 4,
+            max_substep_seconds: 0.01,
+            ..Default::default()
+        };
+        assert_eq!(substep_count(0.016, &config), 4);
+        assert_eq!(substep_count(0.5, &config), 50);
     }
 }
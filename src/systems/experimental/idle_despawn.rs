@@ -0,0 +1,82 @@
+use amethyst::{core::timing::Time, ecs::*};
+
+use crate::{
+    components::{creatures::Movement, idle_timer::IdleTimer},
+    resources::{spawn_stats::SpawnStats, topplegrass::TopplegrassConfig},
+};
+
+/// Deletes entities with an IdleTimer that have moved slower than
+/// TopplegrassConfig::idle_speed_threshold for longer than TopplegrassConfig::idle_timeout.
+/// Without wind, topplegrass would otherwise never reach the edge of the world and pile up.
+#[derive(Default)]
+pub struct IdleDespawnSystem;
+
+impl<'s> System<'s> for IdleDespawnSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, IdleTimer>,
+        ReadStorage<'s, Movement>,
+        Read<'s, Time>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, SpawnStats>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, mut idle_timers, movements, time, config, mut spawn_stats): Self::SystemData,
+    ) {
+        for (entity, idle_timer, movement) in (&entities, &mut idle_timers, &movements).join() {
+            let horizontal_speed = movement.velocity.xy().magnitude();
+            if horizontal_speed >= config.idle_speed_threshold {
+                idle_timer.idle_seconds = 0.0;
+                continue;
+            }
+            idle_timer.idle_seconds += time.delta_seconds();
+            if idle_timer.idle_seconds >= config.idle_timeout && entities.delete(entity).is_ok() {
+                spawn_stats.record_despawn();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn stationary_entity_is_despawned_after_the_timeout() {
+        let mut world = World::new();
+        let mut system = IdleDespawnSystem::default();
+        System::setup(&mut system, &mut world);
+        world.write_resource::<TopplegrassConfig>().idle_timeout = 2.0;
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        let stationary = world
+            .create_entity()
+            .with(IdleTimer::default())
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 5.0,
+            })
+            .build();
+        let rolling = world
+            .create_entity()
+            .with(IdleTimer::default())
+            .with(Movement {
+                velocity: Vector3::new(2.0, 0.0, 0.0),
+                max_movement_speed: 5.0,
+            })
+            .build();
+
+        for _ in 0..3 {
+            system.run_now(&world);
+            world.maintain();
+        }
+        assert!(world.is_alive(stationary));
+
+        system.run_now(&world);
+        world.maintain();
+        assert!(!world.is_alive(stationary));
+        assert!(world.is_alive(rolling));
+    }
+}
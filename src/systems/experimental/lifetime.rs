@@ -0,0 +1,25 @@
+use amethyst::{core::timing::Time, ecs::*};
+
+use crate::{components::lifetime::Lifetime, resources::spawn_stats::SpawnStats};
+
+/// Counts down the Lifetime of entities and deletes them once it has run out.
+#[derive(Default)]
+pub struct LifetimeSystem;
+
+impl<'s> System<'s> for LifetimeSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Lifetime>,
+        Read<'s, Time>,
+        Write<'s, SpawnStats>,
+    );
+
+    fn run(&mut self, (entities, mut lifetimes, time, mut spawn_stats): Self::SystemData) {
+        for (entity, lifetime) in (&entities, &mut lifetimes).join() {
+            lifetime.remaining -= time.delta_seconds();
+            if lifetime.remaining <= 0.0 && entities.delete(entity).is_ok() {
+                spawn_stats.record_despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use amethyst::{
+    core::{math::Vector2, transform::Transform},
+    ecs::*,
+};
+
+use crate::{
+    components::creatures::TopplegrassTag,
+    resources::{minimap::MinimapData, world_bounds::WorldBounds},
+};
+
+/// Collects the normalized positions of all topplegrass into the MinimapData resource.
+#[derive(Default)]
+pub struct MinimapSystem;
+
+impl<'s> System<'s> for MinimapSystem {
+    type SystemData = (
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, TopplegrassTag>,
+        ReadExpect<'s, WorldBounds>,
+        Write<'s, MinimapData>,
+    );
+
+    fn run(&mut self, (transforms, topplegrass_tags, bounds, mut minimap): Self::SystemData) {
+        minimap.topplegrass_positions.clear();
+        for (transform, _) in (&transforms, &topplegrass_tags).join() {
+            let translation = transform.translation();
+            minimap.topplegrass_positions.push(normalize_position(
+                translation.x,
+                translation.y,
+                &bounds,
+            ));
+        }
+    }
+}
+
+/// Maps a position within the world bounds to [0, 1] x [0, 1]. Positions outside of the bounds
+/// are clamped to the edge of the map.
+fn normalize_position(x: f32, y: f32, bounds: &WorldBounds) -> Vector2<f32> {
+    let normalize = |value: f32, min: f32, max: f32| {
+        if max - min > std::f32::EPSILON {
+            ((value - min) / (max - min)).max(0.0).min(1.0)
+        } else {
+            0.5
+        }
+    };
+    Vector2::new(
+        normalize(x, bounds.left, bounds.right),
+        normalize(y, bounds.bottom, bounds.top),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn center_of_the_bounds_maps_to_center_of_the_map() {
+        let bounds = WorldBounds::new(-10.0, 30.0, 0.0, 10.0);
+        assert_eq!(
+            normalize_position(10.0, 5.0, &bounds),
+            Vector2::new(0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn positions_outside_the_bounds_are_clamped() {
+        let bounds = WorldBounds::new(-10.0, 10.0, -10.0, 10.0);
+        assert_eq!(
+            normalize_position(25.0, -11.0, &bounds),
+            Vector2::new(1.0, 0.0)
+        );
+    }
+}
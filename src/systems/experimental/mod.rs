@@ -1,5 +1,25 @@
+pub mod debris;
+pub mod distance_cull;
+pub mod fan;
 pub mod gravity;
+pub mod idle_despawn;
+pub mod lifetime;
+pub mod minimap;
 pub mod out_of_bounds;
 pub mod perception;
+pub mod rolling_sound;
+pub mod scent;
+pub mod seeds;
+pub mod spawn_animation;
+pub mod spawn_location;
 pub mod topplegrass;
+pub mod topplegrass_knock;
+pub mod topplegrass_merge;
 pub mod wind_control;
+pub mod wind_force;
+pub mod wind_hud;
+pub mod wind_magnitude;
+pub mod wind_migration;
+pub mod wind_shadow;
+pub mod wind_smoothing;
+pub mod wind_source;
@@ -1,10 +1,12 @@
-use crate::resources::world_bounds::WorldBounds;
+use crate::resources::{
+    despawn::DespawnConfig, spawn_stats::SpawnStats, world_bounds::WorldBounds,
+};
 use amethyst::{core::transform::components::Transform, ecs::*};
 
-use crate::components::creatures::DespawnWhenOutOfBoundsTag;
+use crate::components::creatures::{DespawnWhenOutOfBoundsTag, Species};
 
 /// Deletes any entity tagged with DespawnWhenOutOfBoundsTag if they are detected to be outside
-/// the world bounds.
+/// the world bounds, by more than the margin the DespawnConfig sets for their species.
 #[derive(Default)]
 pub struct OutOfBoundsDespawnSystem;
 
@@ -13,19 +15,63 @@ impl<'s> System<'s> for OutOfBoundsDespawnSystem {
         Entities<'s>,
         ReadStorage<'s, Transform>,
         ReadStorage<'s, DespawnWhenOutOfBoundsTag>,
+        ReadStorage<'s, Species>,
         ReadExpect<'s, WorldBounds>,
+        Read<'s, DespawnConfig>,
+        Write<'s, SpawnStats>,
     );
 
-    fn run(&mut self, (entities, locals, tags, bounds): Self::SystemData) {
-        for (entity, local, _) in (&*entities, &locals, &tags).join() {
+    fn run(
+        &mut self,
+        (entities, locals, tags, species, bounds, config, mut spawn_stats): Self::SystemData,
+    ) {
+        for (entity, local, species, _) in (&*entities, &locals, species.maybe(), &tags).join() {
             let pos = local.translation();
-            if pos.x > bounds.right
-                || pos.x < bounds.left
-                || pos.y > bounds.top
-                || pos.y < bounds.bottom
-            {
-                let _ = entities.delete(entity);
+            let margin = config.margin(species.map(|species| &species.0));
+            let out_of_bounds = pos.x > bounds.right + margin
+                || pos.x < bounds.left - margin
+                || pos.y > bounds.top + margin
+                || pos.y < bounds.bottom - margin;
+            if out_of_bounds && entities.delete(entity).is_ok() {
+                spawn_stats.record_despawn();
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_species_despawns_at_its_own_margin() {
+        let mut world = World::new();
+        let mut system = OutOfBoundsDespawnSystem;
+        System::setup(&mut system, &mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        let mut config = DespawnConfig::default();
+        config.margins.insert("Debris".to_string(), 0.0);
+        config.margins.insert("Carnivore".to_string(), 3.0);
+        world.insert(config);
+        let mut beyond_the_edge = |creature_type: &str, x: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, 0.0, 0.0);
+            world
+                .create_entity()
+                .with(transform)
+                .with(DespawnWhenOutOfBoundsTag)
+                .with(Species(creature_type.to_string()))
+                .build()
+        };
+        let debris = beyond_the_edge("Debris", 11.0);
+        let near_carnivore = beyond_the_edge("Carnivore", 12.0);
+        let far_carnivore = beyond_the_edge("Carnivore", 14.0);
+
+        system.run_now(&world);
+        world.maintain();
+
+        assert!(!world.entities().is_alive(debris));
+        assert!(world.entities().is_alive(near_carnivore));
+        assert!(!world.entities().is_alive(far_carnivore));
+    }
+}
@@ -0,0 +1,151 @@
+use amethyst::ecs::*;
+
+use std::collections::HashSet;
+
+use crate::{
+    components::creatures::{FallingTag, Movement, TopplegrassTag},
+    resources::{audio::RollingSounds, topplegrass::TopplegrassConfig},
+};
+
+/// Pitch of the rolling sound at TopplegrassConfig::rolling_sound_full_speed, relative to its
+/// pitch at rolling_sound_min_speed.
+const MAX_PITCH: f32 = 1.5;
+
+/// Plays a rolling sound for every topplegrass that rolls over the ground faster than
+/// TopplegrassConfig::rolling_sound_min_speed, with a volume and pitch that grow with its speed.
+/// The sound stops when the topplegrass slows down, jumps or is deleted.
+#[derive(Default)]
+pub struct RollingSoundSystem {
+    playing: HashSet<Entity>,
+}
+
+impl<'s> System<'s> for RollingSoundSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Movement>,
+        ReadStorage<'s, TopplegrassTag>,
+        ReadStorage<'s, FallingTag>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, RollingSounds>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, movements, topplegrass_tags, falling_tags, config, mut sounds): Self::SystemData,
+    ) {
+        let mut still_playing = HashSet::new();
+        for (entity, movement, _, _) in
+            (&entities, &movements, &topplegrass_tags, !&falling_tags).join()
+        {
+            let volume = rolling_volume(movement.velocity.xy().magnitude(), &config);
+            if volume > 0.0 {
+                sounds
+                    .sink
+                    .play(entity, volume, 1.0 + (MAX_PITCH - 1.0) * volume);
+                still_playing.insert(entity);
+            }
+        }
+        for entity in self.playing.difference(&still_playing) {
+            sounds.sink.stop(*entity);
+        }
+        self.playing = still_playing;
+    }
+}
+
+/// Volume of the rolling sound of a topplegrass moving at the given speed, between 0.0 at or
+/// below TopplegrassConfig::rolling_sound_min_speed and 1.0 at rolling_sound_full_speed.
+pub fn rolling_volume(speed: f32, config: &TopplegrassConfig) -> f32 {
+    if speed <= config.rolling_sound_min_speed {
+        return 0.0;
+    }
+    let range = config.rolling_sound_full_speed - config.rolling_sound_min_speed;
+    if range <= 0.0 {
+        return 1.0;
+    }
+    ((speed - config.rolling_sound_min_speed) / range).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::audio::RollingSoundSink;
+    use amethyst::core::math::Vector3;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps track of which entities are playing a sound, and at which volume.
+    #[derive(Clone, Default)]
+    struct MockSink {
+        playing: Arc<Mutex<HashMap<Entity, f32>>>,
+    }
+
+    impl RollingSoundSink for MockSink {
+        fn play(&mut self, entity: Entity, volume: f32, _pitch: f32) {
+            self.playing.lock().unwrap().insert(entity, volume);
+        }
+
+        fn stop(&mut self, entity: Entity) {
+            self.playing.lock().unwrap().remove(&entity);
+        }
+    }
+
+    fn config() -> TopplegrassConfig {
+        TopplegrassConfig {
+            rolling_sound_min_speed: 1.0,
+            rolling_sound_full_speed: 3.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn volume_grows_with_speed() {
+        let config = config();
+        assert_eq!(rolling_volume(0.5, &config), 0.0);
+        assert_eq!(rolling_volume(1.0, &config), 0.0);
+        assert!((rolling_volume(2.0, &config) - 0.5).abs() < 1e-6);
+        assert_eq!(rolling_volume(3.0, &config), 1.0);
+        assert_eq!(rolling_volume(10.0, &config), 1.0);
+    }
+
+    #[test]
+    fn airborne_topplegrass_makes_no_rolling_sound() {
+        let mut world = World::new();
+        let mut system = RollingSoundSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(config());
+        let sink = MockSink::default();
+        world.insert(RollingSounds {
+            sink: Box::new(sink.clone()),
+        });
+        let movement = || Movement {
+            velocity: Vector3::new(2.0, 0.0, 0.0),
+            max_movement_speed: 10.0,
+        };
+        let rolling = world
+            .create_entity()
+            .with(movement())
+            .with(TopplegrassTag)
+            .build();
+        let airborne = world
+            .create_entity()
+            .with(movement())
+            .with(TopplegrassTag)
+            .with(FallingTag)
+            .build();
+
+        system.run_now(&world);
+        {
+            let playing = sink.playing.lock().unwrap();
+            assert!(playing.contains_key(&rolling));
+            assert!(!playing.contains_key(&airborne));
+        }
+
+        // Once the rolling topplegrass jumps, its sound stops.
+        world
+            .write_storage::<FallingTag>()
+            .insert(rolling, FallingTag)
+            .unwrap();
+        system.run_now(&world);
+        assert!(sink.playing.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    ecs::*,
+};
+
+use crate::{components::scent::ScentEmitter, resources::scent::ScentGrid};
+
+/// Lets the scent in the ScentGrid decay, then has every ScentEmitter deposit scent in the cell
+/// it currently occupies.
+#[derive(Default)]
+pub struct ScentSystem;
+
+impl<'s> System<'s> for ScentSystem {
+    type SystemData = (
+        ReadStorage<'s, ScentEmitter>,
+        ReadStorage<'s, Transform>,
+        Read<'s, Time>,
+        Write<'s, ScentGrid>,
+    );
+
+    fn run(&mut self, (emitters, transforms, time, mut scent_grid): Self::SystemData) {
+        let delta_seconds = time.delta_seconds();
+        scent_grid.decay(delta_seconds);
+        for (emitter, transform) in (&emitters, &transforms).join() {
+            let translation = transform.translation();
+            scent_grid.deposit(
+                translation.x,
+                translation.y,
+                emitter.strength * delta_seconds,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn emitters_deposit_scent_at_their_position() {
+        let mut world = World::new();
+        let mut system = ScentSystem::default();
+        System::setup(&mut system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        let mut transform = Transform::default();
+        transform.set_translation(Vector3::new(3.5, -2.5, 0.0));
+        world
+            .create_entity()
+            .with(ScentEmitter { strength: 2.0 })
+            .with(transform)
+            .build();
+
+        system.run_now(&world);
+
+        let scent_grid = world.read_resource::<ScentGrid>();
+        assert_eq!(scent_grid.scent_at(3.5, -2.5), 1.0);
+        assert_eq!(scent_grid.scent_at(0.0, 0.0), 0.0);
+    }
+}
@@ -0,0 +1,159 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    ecs::*,
+    shrev::EventChannel,
+};
+
+use rand::Rng;
+
+use crate::{
+    components::{creatures::TopplegrassTag, seed::Seed},
+    resources::{game_rng::GameRng, topplegrass::TopplegrassConfig},
+    systems::spawner::CreatureSpawnEvent,
+};
+
+/// Makes topplegrass drop seeds on the ground as it rolls along. Every topplegrass drops
+/// TopplegrassConfig::seed_drop_rate seeds per second on average.
+#[derive(Default)]
+pub struct SeedDropSystem;
+
+impl<'s> System<'s> for SeedDropSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, TopplegrassTag>,
+        Read<'s, LazyUpdate>,
+        Read<'s, Time>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, transforms, topple_tags, lazy_update, time, config, mut rng): Self::SystemData,
+    ) {
+        if config.seed_drop_rate <= 0.0 {
+            return;
+        }
+        let drop_chance = config.seed_drop_rate * time.delta_seconds();
+        for (transform, _) in (&transforms, &topple_tags).join() {
+            if rng.gen::<f32>() >= drop_chance {
+                continue;
+            }
+            let translation = transform.translation();
+            let mut seed_transform = Transform::default();
+            seed_transform.set_translation_xyz(translation.x, translation.y, 0.0);
+            lazy_update
+                .create_entity(&entities)
+                .with(seed_transform)
+                .with(Seed::new(config.germination_delay))
+                .build();
+        }
+    }
+}
+
+/// Counts down the seeds on the ground. Once a seed is due, it is removed and, with a chance of
+/// TopplegrassConfig::germination_chance, a plant grows in its place.
+#[derive(Default)]
+pub struct GerminationSystem;
+
+impl<'s> System<'s> for GerminationSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Seed>,
+        ReadStorage<'s, Transform>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        Read<'s, Time>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, GameRng>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut seeds,
+            transforms,
+            lazy_update,
+            mut spawn_events,
+            time,
+            config,
+            mut rng,
+        ): Self::SystemData,
+    ) {
+        for (entity, seed, transform) in (&entities, &mut seeds, &transforms).join() {
+            seed.time_to_germinate -= time.delta_seconds();
+            if seed.time_to_germinate > 0.0 {
+                continue;
+            }
+            let _ = entities.delete(entity);
+            if rng.gen::<f32>() >= config.germination_chance {
+                continue;
+            }
+            let plant = lazy_update
+                .create_entity(&entities)
+                .with(transform.clone())
+                .build();
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Plant".to_string(),
+                entity: plant,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_germinates_into_a_plant_after_the_delay() {
+        let mut world = World::new();
+        let mut system = GerminationSystem;
+        System::setup(&mut system, &mut world);
+        world.insert(GameRng::seeded(3));
+        world.insert(TopplegrassConfig {
+            germination_delay: 2.0,
+            germination_chance: 1.0,
+            ..TopplegrassConfig::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(3.0, -2.0, 0.0);
+        let seed = world
+            .create_entity()
+            .with(transform)
+            .with(Seed::new(2.0))
+            .build();
+
+        for _ in 0..3 {
+            system.run_now(&world);
+            world.maintain();
+        }
+        assert!(world.is_alive(seed));
+        assert_eq!(
+            world
+                .fetch::<EventChannel<CreatureSpawnEvent>>()
+                .read(&mut reader)
+                .count(),
+            0
+        );
+
+        system.run_now(&world);
+        world.maintain();
+        assert!(!world.is_alive(seed));
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        let events: Vec<_> = spawn_events.read(&mut reader).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].creature_type, "Plant");
+        let transforms = world.read_storage::<Transform>();
+        let plant_translation = transforms.get(events[0].entity).unwrap().translation();
+        assert!((plant_translation.x - 3.0).abs() < 1e-6);
+        assert!((plant_translation.y + 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,73 @@
+use amethyst::{
+    core::{timing::Time, transform::Transform},
+    ecs::*,
+};
+
+use crate::components::spawn_animation::SpawnAnimation;
+
+/// Scales entities with a SpawnAnimation up from zero to their target scale over the duration of
+/// the animation, then removes the SpawnAnimation component.
+#[derive(Default)]
+pub struct SpawnAnimationSystem;
+
+impl<'s> System<'s> for SpawnAnimationSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, SpawnAnimation>,
+        WriteStorage<'s, Transform>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (entities, mut animations, mut transforms, time): Self::SystemData) {
+        let finished = (&entities, &mut animations, &mut transforms)
+            .join()
+            .filter_map(|(entity, animation, transform)| {
+                animation.elapsed += time.delta_seconds();
+                let progress = animation.progress();
+                transform.set_scale(animation.target_scale * progress);
+                if progress >= 1.0 {
+                    Some(entity)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<Entity>>();
+        for entity in finished {
+            animations.remove(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn scale_reaches_target_and_animation_is_removed() {
+        let mut world = World::new();
+        let mut system = SpawnAnimationSystem::default();
+        System::setup(&mut system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(0.25);
+        let target_scale = Vector3::new(2.0, 2.0, 2.0);
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(SpawnAnimation::new(1.0, target_scale))
+            .build();
+
+        system.run_now(&world);
+        {
+            let transforms = world.read_storage::<Transform>();
+            let scale = transforms.get(entity).unwrap().scale();
+            assert!((scale - target_scale * 0.25).magnitude() < 1e-6);
+        }
+        for _ in 0..3 {
+            system.run_now(&world);
+        }
+
+        let transforms = world.read_storage::<Transform>();
+        assert!((transforms.get(entity).unwrap().scale() - target_scale).magnitude() < 1e-6);
+        assert!(world.read_storage::<SpawnAnimation>().get(entity).is_none());
+    }
+}
@@ -0,0 +1,746 @@
+use amethyst::core::math::{Vector2, Vector3};
+use rand::{Rng, RngCore};
+use std::{collections::HashMap, f32};
+
+use crate::resources::{
+    terrain::Terrain,
+    topplegrass::{ExclusionZone, TopplegrassConfig},
+    wind::{Cardinal, Wind},
+    world_bounds::WorldBounds,
+};
+
+/// The fractional part of the golden ratio. Stepping along an edge by this fraction of its length
+/// never comes back to the same point, and fills the edge evenly.
+const GOLDEN_RATIO_FRACTION: f32 = 0.618_034;
+/// gen_spawn_location never generates more locations than this, whatever the configured number
+/// of retries, so a badly configured spawner can't stall a frame.
+const MAX_SPAWN_RETRIES: usize = 100;
+
+/// Decides where a spawner places the next entity it spawns. Strategies may keep track of where
+/// they spawned before.
+pub trait SpawnLocationStrategy {
+    fn location(
+        &mut self,
+        wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32>;
+
+    /// Called by the spawner every frame, for strategies that need to keep track of time.
+    fn advance(&mut self, _delta_seconds: f32) {}
+}
+
+/// Spawns at a random point on the world border that the wind is blowing away from. In other
+/// words: upwind from the center of the world.
+pub struct UpwindEdge {
+    pub height: f32,
+}
+
+impl SpawnLocationStrategy for UpwindEdge {
+    fn location(
+        &mut self,
+        wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        upwind_edge_point(wind.wind, bounds, rng.gen(), self.height)
+    }
+}
+
+/// Like UpwindEdge, but instead of picking a random point on the border each time, steps along it
+/// by a fixed fraction of its length, wrapping around at the end. This spreads consecutive spawns
+/// evenly over the border, where random points would sometimes cluster.
+pub struct StratifiedUpwindEdge {
+    pub height: f32,
+    /// Where along the border the previous spawn was, as a fraction of its length.
+    offset: f32,
+}
+
+impl StratifiedUpwindEdge {
+    pub fn new(height: f32) -> StratifiedUpwindEdge {
+        StratifiedUpwindEdge {
+            height,
+            offset: 0.0,
+        }
+    }
+}
+
+impl SpawnLocationStrategy for StratifiedUpwindEdge {
+    fn location(
+        &mut self,
+        wind: &Wind,
+        bounds: &WorldBounds,
+        _rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        self.offset = (self.offset + GOLDEN_RATIO_FRACTION) % 1.0;
+        upwind_edge_point(wind.wind, bounds, self.offset, self.height)
+    }
+}
+
+/// Like UpwindEdge, but divides each border into segments and avoids spawning in a segment that
+/// was used less than cooldown seconds ago, so a steady wind doesn't make topplegrass enter in
+/// lines. If all segments are cooling down, the one used longest ago is picked.
+pub struct SegmentedUpwindEdge {
+    pub height: f32,
+    pub segments: usize,
+    pub cooldown: f32,
+    elapsed: f64,
+    /// For every border, the time each of its segments was last spawned in.
+    last_used: HashMap<Cardinal, Vec<Option<f64>>>,
+}
+
+impl SegmentedUpwindEdge {
+    pub fn new(height: f32, segments: usize, cooldown: f32) -> SegmentedUpwindEdge {
+        SegmentedUpwindEdge {
+            height,
+            segments: segments.max(1),
+            cooldown,
+            elapsed: 0.0,
+            last_used: HashMap::new(),
+        }
+    }
+}
+
+impl SpawnLocationStrategy for SegmentedUpwindEdge {
+    fn location(
+        &mut self,
+        wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        let elapsed = self.elapsed;
+        let cooldown = f64::from(self.cooldown);
+        let segments = self.segments;
+        let last_used = self
+            .last_used
+            .entry(wind.dominant_cardinal())
+            .or_insert_with(|| vec![None; segments]);
+        let available = (0..segments)
+            .filter(|&i| last_used[i].map_or(true, |time| elapsed - time >= cooldown))
+            .collect::<Vec<usize>>();
+        let segment = if available.is_empty() {
+            (0..segments)
+                .min_by(|&a, &b| last_used[a].partial_cmp(&last_used[b]).unwrap())
+                .unwrap()
+        } else {
+            available[rng.gen_range(0, available.len())]
+        };
+        last_used[segment] = Some(elapsed);
+        let fraction = (segment as f32 + rng.gen::<f32>()) / segments as f32;
+        upwind_edge_point(wind.wind, bounds, fraction, self.height)
+    }
+
+    fn advance(&mut self, delta_seconds: f32) {
+        self.elapsed += f64::from(delta_seconds);
+    }
+}
+
+/// Like UpwindEdge, but instead of snapping the wind to the nearest cardinal direction, picks one
+/// of the two upwind borders at random, weighted by how closely the wind blows along each axis
+/// and by the length of each border. A diagonal wind spreads the spawns over both borders,
+/// including the corner between them, and in a wide world the long border gets more of them.
+///
+/// The wind's alignment with each axis is raised to the power of edge_weight_exponent before
+/// weighting. 1.0 weights linearly, higher values make the spawns shift to the dominant border
+/// faster as the wind rotates away from the diagonal, and 0.0 ignores the wind direction entirely.
+pub struct BlendedUpwindEdges {
+    pub height: f32,
+    pub edge_weight_exponent: f32,
+}
+
+impl BlendedUpwindEdges {
+    pub fn new(height: f32) -> BlendedUpwindEdges {
+        BlendedUpwindEdges {
+            height,
+            edge_weight_exponent: 1.0,
+        }
+    }
+}
+
+impl SpawnLocationStrategy for BlendedUpwindEdges {
+    fn location(
+        &mut self,
+        wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        // The left and right borders run along the y axis, so their length is the world's height.
+        if wind.wind.x == 0.0 && wind.wind.y == 0.0 {
+            return upwind_edge_point(wind.wind, bounds, rng.gen(), self.height);
+        }
+        let alignment = |component: f32| {
+            if component == 0.0 {
+                0.0
+            } else {
+                component.abs().powf(self.edge_weight_exponent)
+            }
+        };
+        let weight_x = alignment(wind.wind.x) * (bounds.top - bounds.bottom);
+        let weight_y = alignment(wind.wind.y) * (bounds.right - bounds.left);
+        if weight_x + weight_y <= 0.0 {
+            return upwind_edge_point(wind.wind, bounds, rng.gen(), self.height);
+        }
+        if rng.gen::<f32>() * (weight_x + weight_y) < weight_x {
+            let x = if wind.wind.x > 0.0 {
+                bounds.left
+            } else {
+                bounds.right
+            };
+            Vector3::new(x, rng.gen_range(bounds.bottom, bounds.top), self.height)
+        } else {
+            let y = if wind.wind.y > 0.0 {
+                bounds.bottom
+            } else {
+                bounds.top
+            };
+            Vector3::new(rng.gen_range(bounds.left, bounds.right), y, self.height)
+        }
+    }
+}
+
+/// Spawns at a random point on one of the world borders, ignoring the wind. The border is picked
+/// at random with the given weights for the left, bottom, right and top border, in that order.
+/// The weights are normalized, so only their ratios matter.
+pub struct WeightedEdges {
+    pub height: f32,
+    weights: [f32; 4],
+}
+
+impl WeightedEdges {
+    /// Weights that are all zero, or negative, pick every border equally often.
+    pub fn new(height: f32, weights: [f32; 4]) -> WeightedEdges {
+        let mut weights = weights;
+        for weight in weights.iter_mut() {
+            *weight = weight.max(0.0);
+        }
+        let total: f32 = weights.iter().sum();
+        let weights = if total > 0.0 {
+            [
+                weights[0] / total,
+                weights[1] / total,
+                weights[2] / total,
+                weights[3] / total,
+            ]
+        } else {
+            [0.25; 4]
+        };
+        WeightedEdges { height, weights }
+    }
+}
+
+impl SpawnLocationStrategy for WeightedEdges {
+    fn location(
+        &mut self,
+        _wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        let mut roll = rng.gen::<f32>();
+        // Falls back to the last border with any weight, should rounding leave roll just above
+        // the sum of the weights.
+        let mut edge = self
+            .weights
+            .iter()
+            .rposition(|weight| *weight > 0.0)
+            .unwrap_or(0);
+        for (index, weight) in self.weights.iter().enumerate() {
+            if *weight > 0.0 && roll < *weight {
+                edge = index;
+                break;
+            }
+            roll -= weight;
+        }
+        match edge {
+            0 => Vector3::new(
+                bounds.left,
+                rng.gen_range(bounds.bottom, bounds.top),
+                self.height,
+            ),
+            1 => Vector3::new(
+                rng.gen_range(bounds.left, bounds.right),
+                bounds.bottom,
+                self.height,
+            ),
+            2 => Vector3::new(
+                bounds.right,
+                rng.gen_range(bounds.bottom, bounds.top),
+                self.height,
+            ),
+            _ => Vector3::new(
+                rng.gen_range(bounds.left, bounds.right),
+                bounds.top,
+                self.height,
+            ),
+        }
+    }
+}
+
+/// Spawns at a random point anywhere within the world bounds.
+pub struct RandomInterior {
+    pub height: f32,
+}
+
+impl SpawnLocationStrategy for RandomInterior {
+    fn location(
+        &mut self,
+        _wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        Vector3::new(
+            rng.gen_range(bounds.left, bounds.right),
+            rng.gen_range(bounds.bottom, bounds.top),
+            self.height,
+        )
+    }
+}
+
+/// Spawns at a random point within radius of the center of the world.
+pub struct CenterBurst {
+    pub radius: f32,
+    pub height: f32,
+}
+
+impl SpawnLocationStrategy for CenterBurst {
+    fn location(
+        &mut self,
+        _wind: &Wind,
+        bounds: &WorldBounds,
+        rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        let center_x = (bounds.left + bounds.right) / 2.0;
+        let center_y = (bounds.bottom + bounds.top) / 2.0;
+        let angle = rng.gen_range(0.0, 2.0 * f32::consts::PI);
+        // Taking the square root spreads the points evenly over the area of the circle.
+        let distance = self.radius * rng.gen::<f32>().sqrt();
+        Vector3::new(
+            center_x + distance * angle.cos(),
+            center_y + distance * angle.sin(),
+            self.height,
+        )
+    }
+}
+
+/// Spawns at the middle of one of the world borders, going around the four of them in turn:
+/// left, bottom, right and top. Ignores the wind, so every edge can be checked in a predictable
+/// order.
+pub struct CyclingEdges {
+    pub height: f32,
+    next_edge: usize,
+}
+
+impl CyclingEdges {
+    pub fn new(height: f32) -> CyclingEdges {
+        CyclingEdges {
+            height,
+            next_edge: 0,
+        }
+    }
+}
+
+impl SpawnLocationStrategy for CyclingEdges {
+    fn location(
+        &mut self,
+        _wind: &Wind,
+        bounds: &WorldBounds,
+        _rng: &mut dyn RngCore,
+    ) -> Vector3<f32> {
+        let center_x = (bounds.left + bounds.right) / 2.0;
+        let center_y = (bounds.bottom + bounds.top) / 2.0;
+        let edge = self.next_edge;
+        self.next_edge = (self.next_edge + 1) % 4;
+        match edge {
+            0 => Vector3::new(bounds.left, center_y, self.height),
+            1 => Vector3::new(center_x, bounds.bottom, self.height),
+            2 => Vector3::new(bounds.right, center_y, self.height),
+            _ => Vector3::new(center_x, bounds.top, self.height),
+        }
+    }
+}
+
+/// Where spawns are not allowed to land.
+pub struct SpawnConstraints<'a> {
+    pub exclusion_zones: &'a [ExclusionZone],
+    pub terrain: &'a Terrain,
+    /// Steepest slope of the terrain to spawn on, as the rise per unit of horizontal distance.
+    pub max_slope: f32,
+    /// How many more locations are generated when one is rejected.
+    pub max_retries: usize,
+}
+
+impl<'a> SpawnConstraints<'a> {
+    pub fn new(config: &'a TopplegrassConfig, terrain: &'a Terrain) -> SpawnConstraints<'a> {
+        SpawnConstraints {
+            exclusion_zones: &config.exclusion_zones,
+            terrain,
+            max_slope: config.max_spawn_slope,
+            max_retries: config.exclusion_max_retries,
+        }
+    }
+
+    pub fn allows(&self, location: Vector3<f32>) -> bool {
+        let point = location.xy();
+        self.exclusion_zones
+            .iter()
+            .all(|zone| !zone.contains(point))
+            && self.terrain.slope(point) <= self.max_slope
+    }
+}
+
+/// Asks the strategy for a spawn location that the constraints allow. Locations inside an
+/// exclusion zone or on too steep terrain are rejected and another one is generated, up to
+/// max_retries times, but never more than MAX_SPAWN_RETRIES times. If all of them were rejected,
+/// a warning is logged and the middle of the first world border that the constraints allow is
+/// used instead, or the center of the world if they allow none of those either.
+pub fn gen_spawn_location(
+    strategy: &mut dyn SpawnLocationStrategy,
+    constraints: &SpawnConstraints,
+    wind: &Wind,
+    bounds: &WorldBounds,
+    rng: &mut dyn RngCore,
+) -> Vector3<f32> {
+    let mut height = 0.0;
+    for _ in 0..=constraints.max_retries.min(MAX_SPAWN_RETRIES) {
+        let location = strategy.location(wind, bounds, rng);
+        if constraints.allows(location) {
+            return location;
+        }
+        height = location.z;
+    }
+    let fallback = fallback_spawn_location(constraints, bounds, height);
+    warn!(
+        "No spawn location was found that the spawn constraints allow, falling back to {:?}",
+        fallback
+    );
+    fallback
+}
+
+/// Returns the middle of the first of the left, bottom, right and top world borders that the
+/// constraints allow, or else the center of the world.
+fn fallback_spawn_location(
+    constraints: &SpawnConstraints,
+    bounds: &WorldBounds,
+    height: f32,
+) -> Vector3<f32> {
+    let center_x = (bounds.left + bounds.right) / 2.0;
+    let center_y = (bounds.bottom + bounds.top) / 2.0;
+    let center = Vector3::new(center_x, center_y, height);
+    [
+        Vector3::new(bounds.left, center_y, height),
+        Vector3::new(center_x, bounds.bottom, height),
+        Vector3::new(bounds.right, center_y, height),
+        Vector3::new(center_x, bounds.top, height),
+    ]
+    .iter()
+    .cloned()
+    .find(|location| constraints.allows(*location))
+    .unwrap_or(center)
+}
+
+/// Returns the point at the given fraction along the border that the wind is blowing away from.
+fn upwind_edge_point(
+    wind: Vector2<f32>,
+    bounds: &WorldBounds,
+    fraction: f32,
+    height: f32,
+) -> Vector3<f32> {
+    let along_x = bounds.left + (bounds.right - bounds.left) * fraction;
+    let along_y = bounds.bottom + (bounds.top - bounds.bottom) * fraction;
+    match Cardinal::nearest(wind) {
+        Cardinal::East => Vector3::new(bounds.left, along_y, height),
+        Cardinal::North => Vector3::new(along_x, bounds.bottom, height),
+        Cardinal::West => Vector3::new(bounds.right, along_y, height),
+        Cardinal::South => Vector3::new(along_x, bounds.top, height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::game_rng::GameRng;
+
+    fn bounds() -> WorldBounds {
+        WorldBounds::new(-10.0, 10.0, -5.0, 5.0)
+    }
+
+    #[test]
+    fn upwind_edge_spawns_on_the_border_the_wind_blows_away_from() {
+        let mut strategy = UpwindEdge { height: 0.5 };
+        let mut rng = GameRng::seeded(1);
+        for _ in 0..20 {
+            let east = strategy.location(&Wind::new(2.0, 0.0), &bounds(), &mut rng);
+            assert_eq!(east.x, -10.0);
+            assert!(east.y >= -5.0 && east.y <= 5.0);
+            let south = strategy.location(&Wind::new(0.0, -2.0), &bounds(), &mut rng);
+            assert_eq!(south.y, 5.0);
+            assert!(south.x >= -10.0 && south.x <= 10.0);
+            assert_eq!(south.z, 0.5);
+        }
+    }
+
+    #[test]
+    fn stratified_upwind_edge_spreads_consecutive_spawns() {
+        let mut strategy = StratifiedUpwindEdge::new(0.5);
+        let mut rng = GameRng::seeded(7);
+        let mut positions = (0..10)
+            .map(|_| {
+                let location = strategy.location(&Wind::new(2.0, 0.0), &bounds(), &mut rng);
+                assert_eq!(location.x, -10.0);
+                location.y
+            })
+            .collect::<Vec<f32>>();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // Including the gaps to either end of the 10 unit long border, no stretch of the border
+        // should be left much emptier than the others.
+        let mut gaps = positions
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect::<Vec<f32>>();
+        gaps.push(positions[0] - -5.0);
+        gaps.push(5.0 - positions[positions.len() - 1]);
+        for gap in gaps {
+            assert!(gap < 2.0);
+        }
+    }
+
+    #[test]
+    fn segmented_upwind_edge_avoids_segments_on_cooldown() {
+        let mut strategy = SegmentedUpwindEdge::new(0.5, 4, 10.0);
+        let mut rng = GameRng::seeded(8);
+        let mut segment = |strategy: &mut SegmentedUpwindEdge| {
+            let location = strategy.location(&Wind::new(2.0, 0.0), &bounds(), &mut rng);
+            assert_eq!(location.x, -10.0);
+            // The border runs from -5.0 to 5.0, so every segment is 2.5 long.
+            (((location.y + 5.0) / 2.5) as usize).min(3)
+        };
+        let mut used = Vec::new();
+        for _ in 0..4 {
+            strategy.advance(1.0);
+            let next = segment(&mut strategy);
+            assert!(!used.contains(&next));
+            used.push(next);
+        }
+        // Once the cooldown has passed, the first segment can be used again.
+        strategy.advance(7.5);
+        assert_eq!(segment(&mut strategy), used[0]);
+    }
+
+    #[test]
+    fn blended_upwind_edges_uses_both_borders_for_diagonal_wind() {
+        let mut strategy = BlendedUpwindEdges::new(0.5);
+        let mut rng = GameRng::seeded(4);
+        let mut on_left = 0;
+        let mut on_bottom = 0;
+        for _ in 0..200 {
+            let location = strategy.location(&Wind::new(1.0, 1.0), &bounds(), &mut rng);
+            if location.x == -10.0 {
+                on_left += 1;
+            } else {
+                assert_eq!(location.y, -5.0);
+                on_bottom += 1;
+            }
+        }
+        assert!(on_left > 0);
+        assert!(on_bottom > 0);
+    }
+
+    #[test]
+    fn blended_upwind_edges_favours_the_longer_border() {
+        let mut strategy = BlendedUpwindEdges::new(0.5);
+        let bounds = WorldBounds::new(-50.0, 50.0, -5.0, 5.0);
+        let mut rng = GameRng::seeded(6);
+        let samples = 2000;
+        let mut on_bottom = 0;
+        for _ in 0..samples {
+            let location = strategy.location(&Wind::new(1.0, 1.0), &bounds, &mut rng);
+            if location.y == -5.0 {
+                on_bottom += 1;
+            } else {
+                assert_eq!(location.x, -50.0);
+            }
+        }
+        // The bottom border is ten times as long as the left one, so it should get about 10/11 of
+        // the spawns.
+        let fraction = on_bottom as f32 / samples as f32;
+        assert!(fraction > 0.85 && fraction < 0.96);
+    }
+
+    #[test]
+    fn blended_upwind_edges_exponent_sharpens_the_weighting() {
+        let bounds = WorldBounds::new(-10.0, 10.0, -10.0, 10.0);
+        // Roughly 30 degrees north of east, so the wind is about twice as aligned with the x axis.
+        let wind = Wind::new(2.0, 1.0);
+        let fraction_on_left = |exponent: f32| {
+            let mut strategy = BlendedUpwindEdges {
+                height: 0.5,
+                edge_weight_exponent: exponent,
+            };
+            let mut rng = GameRng::seeded(9);
+            let samples = 2000;
+            let on_left = (0..samples)
+                .filter(|_| strategy.location(&wind, &bounds, &mut rng).x == -10.0)
+                .count();
+            on_left as f32 / samples as f32
+        };
+        // Linear weighting gives the left border about 2/3 of the spawns, squaring gives it 4/5.
+        let linear = fraction_on_left(1.0);
+        let squared = fraction_on_left(2.0);
+        assert!(linear > 0.6 && linear < 0.73);
+        assert!(squared > 0.75 && squared < 0.85);
+        // Without the exponent, both borders are equally likely.
+        let flat = fraction_on_left(0.0);
+        assert!(flat > 0.43 && flat < 0.57);
+    }
+
+    #[test]
+    fn cycling_edges_visits_all_four_edges_in_order() {
+        let mut strategy = CyclingEdges::new(0.5);
+        let mut rng = GameRng::seeded(11);
+        let wind = Wind::new(2.0, 0.0);
+        let locations = (0..5)
+            .map(|_| strategy.location(&wind, &bounds(), &mut rng))
+            .collect::<Vec<Vector3<f32>>>();
+        assert_eq!(locations[0], Vector3::new(-10.0, 0.0, 0.5));
+        assert_eq!(locations[1], Vector3::new(0.0, -5.0, 0.5));
+        assert_eq!(locations[2], Vector3::new(10.0, 0.0, 0.5));
+        assert_eq!(locations[3], Vector3::new(0.0, 5.0, 0.5));
+        // And back to the start.
+        assert_eq!(locations[4], locations[0]);
+    }
+
+    #[test]
+    fn weighted_edges_follow_the_weights_regardless_of_the_wind() {
+        let wind = Wind::new(-2.0, 1.0);
+        let mut rng = GameRng::seeded(5);
+        let mut left_only = WeightedEdges::new(0.5, [1.0, 0.0, 0.0, 0.0]);
+        for _ in 0..100 {
+            let location = left_only.location(&wind, &bounds(), &mut rng);
+            assert_eq!(location.x, -10.0);
+        }
+
+        let mut balanced = WeightedEdges::new(0.5, [2.0, 2.0, 2.0, 2.0]);
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let location = balanced.location(&wind, &bounds(), &mut rng);
+            let edge = if location.x == -10.0 {
+                0
+            } else if location.y == -5.0 {
+                1
+            } else if location.x == 10.0 {
+                2
+            } else {
+                assert_eq!(location.y, 5.0);
+                3
+            };
+            counts[edge] += 1;
+        }
+        for count in counts.iter() {
+            assert!(*count > 850 && *count < 1150, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn spawns_never_land_inside_an_exclusion_zone() {
+        let zones = vec![
+            ExclusionZone {
+                center: Vector2::new(0.0, 0.0),
+                radius: 3.0,
+            },
+            ExclusionZone {
+                center: Vector2::new(-8.0, 2.0),
+                radius: 2.0,
+            },
+        ];
+        let mut strategy = RandomInterior { height: 0.5 };
+        let mut rng = GameRng::seeded(10);
+        let wind = Wind::new(2.0, 0.0);
+        let terrain = Terrain::default();
+        let constraints = SpawnConstraints {
+            exclusion_zones: &zones,
+            terrain: &terrain,
+            max_slope: 1.0,
+            max_retries: 10,
+        };
+        for _ in 0..1000 {
+            let location =
+                gen_spawn_location(&mut strategy, &constraints, &wind, &bounds(), &mut rng);
+            assert!(zones.iter().all(|zone| !zone.contains(location.xy())));
+        }
+    }
+
+    #[test]
+    fn impossible_constraints_fall_back_instead_of_hanging() {
+        // The whole world is excluded, and far more retries are allowed than could ever run.
+        let everywhere = vec![ExclusionZone {
+            center: Vector2::new(0.0, 0.0),
+            radius: 100.0,
+        }];
+        let terrain = Terrain::default();
+        let constraints = SpawnConstraints {
+            exclusion_zones: &everywhere,
+            terrain: &terrain,
+            max_slope: 1.0,
+            max_retries: usize::MAX,
+        };
+        let mut strategy = RandomInterior { height: 0.5 };
+        let mut rng = GameRng::seeded(13);
+        let wind = Wind::new(2.0, 0.0);
+        assert_eq!(
+            gen_spawn_location(&mut strategy, &constraints, &wind, &bounds(), &mut rng),
+            Vector3::new(0.0, 0.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn spawns_avoid_steep_terrain() {
+        // The left half of the world is a cliff rising by 2 per unit, the right half is flat.
+        let terrain = Terrain {
+            origin: Vector2::new(-10.0, -5.0),
+            cell_size: 10.0,
+            columns: 3,
+            heights: vec![0.0, 20.0, 20.0, 0.0, 20.0, 20.0],
+        };
+        let constraints = SpawnConstraints {
+            exclusion_zones: &[],
+            terrain: &terrain,
+            max_slope: 1.0,
+            max_retries: 20,
+        };
+        let mut strategy = RandomInterior { height: 0.5 };
+        let mut rng = GameRng::seeded(12);
+        let wind = Wind::new(2.0, 0.0);
+        for _ in 0..1000 {
+            let location =
+                gen_spawn_location(&mut strategy, &constraints, &wind, &bounds(), &mut rng);
+            assert!(location.x >= 0.0);
+        }
+    }
+
+    #[test]
+    fn random_interior_spawns_within_the_bounds() {
+        let mut strategy = RandomInterior { height: 0.5 };
+        let mut rng = GameRng::seeded(2);
+        for _ in 0..100 {
+            let location = strategy.location(&Wind::default(), &bounds(), &mut rng);
+            assert!(location.x >= -10.0 && location.x <= 10.0);
+            assert!(location.y >= -5.0 && location.y <= 5.0);
+            assert_eq!(location.z, 0.5);
+        }
+    }
+
+    #[test]
+    fn center_burst_spawns_within_radius_of_the_center() {
+        let mut strategy = CenterBurst {
+            radius: 2.0,
+            height: 0.5,
+        };
+        let bounds = WorldBounds::new(0.0, 10.0, 0.0, 20.0);
+        let mut rng = GameRng::seeded(3);
+        for _ in 0..100 {
+            let location = strategy.location(&Wind::default(), &bounds, &mut rng);
+            let distance = Vector2::new(location.x - 5.0, location.y - 10.0).magnitude();
+            assert!(distance <= 2.0 + 1e-5);
+            assert_eq!(location.z, 0.5);
+        }
+    }
+}
@@ -6,15 +6,42 @@ use amethyst::{
         transform::components::Transform,
     },
     ecs::*,
-    shrev::EventChannel,
+    shrev::{EventChannel, ReaderId},
 };
 
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use std::f32;
 
+use crate::systems::spawn_location::{
+    gen_spawn_location, BlendedUpwindEdges, CyclingEdges, RandomInterior, SegmentedUpwindEdge,
+    SpawnConstraints, SpawnLocationStrategy, StratifiedUpwindEdge, UpwindEdge, WeightedEdges,
+};
 use crate::{
-    components::creatures::FallingTag, components::creatures::Movement,
-    components::creatures::TopplegrassTag, resources::wind::Wind,
+    components::collider::Circle,
+    components::creatures::DragProfile,
+    components::creatures::FallingTag,
+    components::creatures::Movement,
+    components::creatures::RestingTag,
+    components::creatures::SpawnVelocityTag,
+    components::creatures::TopplegrassTag,
+    components::grounded_timer::GroundedTimer,
+    components::idle_timer::IdleTimer,
+    components::pinned::Pinned,
+    components::spawn_animation::SpawnAnimation,
+    components::topplegrass_variant::TopplegrassVariantId,
+    components::wind_shadow::WindShadow,
+    components::wobble::Wobble,
+    resources::audio::{SoundEvent, SoundKind},
+    resources::game_rng::GameRng,
+    resources::physics::PhysicsConfig,
+    resources::sandbox_mode::SandboxMode,
+    resources::spawning_enabled::SpawningEnabled,
+    resources::terrain::Terrain,
+    resources::topplegrass::{TopplegrassConfig, TopplegrassVariant},
+    resources::two_dimensional_mode::TwoDimensionalMode,
+    resources::wind::Wind,
+    resources::wind_field::WindField,
+    systems::debug_input::DebugCommand,
     systems::spawner::CreatureSpawnEvent,
 };
 
@@ -23,8 +50,12 @@ use crate::{
 /// a spawn internal of at least a few minutes might be better.
 const SPAWN_INTERVAL: f32 = 10.0;
 /// The standard scaling to apply to the entity.
-const TOPPLEGRASS_BASE_SCALE: f32 = 0.002;
-/// At which height the topplegrass entity should spawn.
+pub const TOPPLEGRASS_BASE_SCALE: f32 = 0.002;
+/// The radius of the collider of a topplegrass of the standard scale.
+pub const TOPPLEGRASS_COLLIDER_RADIUS: f32 = 0.25;
+/// How long it takes for a freshly spawned topplegrass to grow to its full size, in seconds.
+const SPAWN_ANIMATION_DURATION: f32 = 0.5;
+/// At which height the topplegrass entity should spawn. Should match PhysicsConfig::ground_height.
 const HEIGHT: f32 = 0.5;
 /// If we knew the radius of the toppleweed, we could calculate the perfect angular velocity,
 /// but instead we'll use this magic value we got through trial and error.
@@ -39,9 +70,75 @@ const JUMP_THRESHOLD: f32 = 1.0;
 const JUMP_PROBABILITY: f32 = 4.0;
 
 /// Periodically schedules a Topplegrass entity to be spawned in through a CreatureSpawnEvent.
-#[derive(Default)]
+/// Where it spawns is decided by the system's SpawnLocationStrategy, which defaults to the
+/// upwind edge of the world.
+/// On its first run, it also spawns TopplegrassConfig::initial_population topplegrass at random
+/// positions within the world bounds, so the world doesn't start out empty.
+/// In SandboxMode, topplegrass only spawns when the SpawnTopplegrass debug command is issued.
+/// While SpawningEnabled is off, no topplegrass spawns at all.
+/// The SpawnTopplegrassAtEdge debug command spawns one in the middle of the next world border,
+/// going around all four in turn.
+/// The ResetSim debug command restarts the spawn timer and spawns the initial population again.
+/// Nothing spawns inside TopplegrassConfig::exclusion_zones, or on Terrain steeper than
+/// TopplegrassConfig::max_spawn_slope, unless no other location can be found (see
+/// gen_spawn_location).
+/// Spawns are accompanied by a SoundEvent at the spawn location, at most once every
+/// TopplegrassConfig::spawn_sound_interval seconds.
 pub struct TopplegrassSpawnSystem {
     secs_to_next_spawn: f32,
+    secs_since_spawn_sound: f32,
+    initial_burst_done: bool,
+    strategy: Box<dyn SpawnLocationStrategy + Send + Sync>,
+    edge_cycle: CyclingEdges,
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+}
+
+impl TopplegrassSpawnSystem {
+    pub fn with_strategy(
+        strategy: Box<dyn SpawnLocationStrategy + Send + Sync>,
+    ) -> TopplegrassSpawnSystem {
+        TopplegrassSpawnSystem {
+            secs_to_next_spawn: 0.0,
+            secs_since_spawn_sound: f32::INFINITY,
+            initial_burst_done: false,
+            strategy,
+            edge_cycle: CyclingEdges::new(HEIGHT),
+            command_reader_id: None,
+        }
+    }
+
+    /// Uses the WeightedEdges strategy if TopplegrassConfig::spawn_edge_weights is set, which
+    /// overrides the wind-based strategies. Otherwise it uses the BlendedUpwindEdges strategy if
+    /// TopplegrassConfig::blend_spawn_edges is set, the
+    /// StratifiedUpwindEdge strategy if TopplegrassConfig::stratified_spawns is set, the
+    /// SegmentedUpwindEdge strategy if TopplegrassConfig::spawn_segments is set, and the default
+    /// UpwindEdge strategy otherwise.
+    pub fn from_config(config: &TopplegrassConfig) -> TopplegrassSpawnSystem {
+        if let Some(weights) = config.spawn_edge_weights {
+            TopplegrassSpawnSystem::with_strategy(Box::new(WeightedEdges::new(HEIGHT, weights)))
+        } else if config.blend_spawn_edges {
+            TopplegrassSpawnSystem::with_strategy(Box::new(BlendedUpwindEdges {
+                height: HEIGHT,
+                edge_weight_exponent: config.spawn_edge_weight_exponent,
+            }))
+        } else if config.stratified_spawns {
+            TopplegrassSpawnSystem::with_strategy(Box::new(StratifiedUpwindEdge::new(HEIGHT)))
+        } else if config.spawn_segments > 0 {
+            TopplegrassSpawnSystem::with_strategy(Box::new(SegmentedUpwindEdge::new(
+                HEIGHT,
+                config.spawn_segments,
+                config.spawn_segment_cooldown,
+            )))
+        } else {
+            TopplegrassSpawnSystem::default()
+        }
+    }
+}
+
+impl Default for TopplegrassSpawnSystem {
+    fn default() -> Self {
+        TopplegrassSpawnSystem::with_strategy(Box::new(UpwindEdge { height: HEIGHT }))
+    }
 }
 
 impl<'s> System<'s> for TopplegrassSpawnSystem {
@@ -52,30 +149,214 @@ impl<'s> System<'s> for TopplegrassSpawnSystem {
         Read<'s, Time>,
         Read<'s, WorldBounds>,
         Read<'s, Wind>,
+        Read<'s, TopplegrassConfig>,
+        Write<'s, GameRng>,
+        Read<'s, EventChannel<DebugCommand>>,
+        Read<'s, SandboxMode>,
+        Write<'s, EventChannel<SoundEvent>>,
+        Read<'s, Terrain>,
+        Read<'s, SpawningEnabled>,
     );
 
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+    }
+
     fn run(
         &mut self,
-        (entities, lazy_update, mut spawn_events, time, world_bounds, wind): Self::SystemData,
+        (
+            entities,
+            lazy_update,
+            mut spawn_events,
+            time,
+            world_bounds,
+            wind,
+            config,
+            mut rng,
+            debug_commands,
+            sandbox_mode,
+            mut sound_events,
+            terrain,
+            spawning_enabled,
+        ): Self::SystemData,
     ) {
-        if self.ready_to_spawn(time.delta_seconds()) {
-            let mut transform = Transform::default();
-            transform.set_scale(Vector3::new(
-                TOPPLEGRASS_BASE_SCALE,
-                TOPPLEGRASS_BASE_SCALE,
-                TOPPLEGRASS_BASE_SCALE,
-            ));
-            transform.append_translation(Self::gen_spawn_location(&wind, &world_bounds));
-            let entity = lazy_update.create_entity(&entities).with(transform).build();
-            spawn_events.single_write(CreatureSpawnEvent {
-                creature_type: "Topplegrass".to_string(),
-                entity,
-            });
+        let constraints = SpawnConstraints::new(&config, &terrain);
+        self.strategy.advance(time.delta_seconds());
+        self.secs_since_spawn_sound += time.delta_seconds();
+        let mut requested = 0;
+        let mut requested_at_edge = 0;
+        for command in debug_commands.read(self.command_reader_id.as_mut().unwrap()) {
+            match command {
+                DebugCommand::SpawnTopplegrass => requested += 1,
+                DebugCommand::SpawnTopplegrassAtEdge => requested_at_edge += 1,
+                DebugCommand::ResetSim => {
+                    self.secs_to_next_spawn = 0.0;
+                    self.initial_burst_done = false;
+                }
+                _ => (),
+            }
+        }
+        if !spawning_enabled.0 {
+            // Don't spawn the initial population when spawning is enabled again later on.
+            self.initial_burst_done = true;
+            return;
+        }
+        let ready_to_spawn = !sandbox_mode.0 && self.ready_to_spawn(time.delta_seconds());
+        let mut context = SpawnContext {
+            entities: &entities,
+            lazy_update: &lazy_update,
+            spawn_events: &mut spawn_events,
+            sound_events: &mut sound_events,
+            wind: &wind,
+            world_bounds: &world_bounds,
+            config: &config,
+            rng: &mut rng,
+            secs_since_spawn_sound: &mut self.secs_since_spawn_sound,
+        };
+        for _ in 0..requested {
+            context.spawn(&mut *self.strategy, Some(&constraints));
+        }
+        for _ in 0..requested_at_edge {
+            context.spawn(&mut self.edge_cycle, None);
+        }
+        if sandbox_mode.0 {
+            // Don't spawn the initial population when leaving sandbox mode later on.
+            self.initial_burst_done = true;
+            return;
+        }
+        if !self.initial_burst_done {
+            self.initial_burst_done = true;
+            let mut interior = RandomInterior { height: HEIGHT };
+            for _ in 0..config.initial_population {
+                context.spawn(&mut interior, Some(&constraints));
+            }
+        }
+        if ready_to_spawn {
+            context.spawn(&mut *self.strategy, Some(&constraints));
         }
     }
 }
 
+/// The resources the TopplegrassSpawnSystem needs to spawn topplegrass during one of its runs.
+struct SpawnContext<'a, 's> {
+    entities: &'a Entities<'s>,
+    lazy_update: &'a LazyUpdate,
+    spawn_events: &'a mut EventChannel<CreatureSpawnEvent>,
+    sound_events: &'a mut EventChannel<SoundEvent>,
+    wind: &'a Wind,
+    world_bounds: &'a WorldBounds,
+    config: &'a TopplegrassConfig,
+    rng: &'a mut GameRng,
+    secs_since_spawn_sound: &'a mut f32,
+}
+
+impl<'a, 's> SpawnContext<'a, 's> {
+    /// Spawns a single topplegrass of a random variant, with a random wobble phase, at a location
+    /// picked by the strategy. If constraints are given, the location is picked through
+    /// gen_spawn_location so the constraints are respected.
+    fn spawn(
+        &mut self,
+        strategy: &mut dyn SpawnLocationStrategy,
+        constraints: Option<&SpawnConstraints>,
+    ) {
+        let location = match constraints {
+            Some(constraints) => gen_spawn_location(
+                strategy,
+                constraints,
+                self.wind,
+                self.world_bounds,
+                &mut *self.rng,
+            ),
+            None => strategy.location(self.wind, self.world_bounds, &mut *self.rng),
+        };
+        let wobble_phase = self.rng.gen_range(0.0, 2.0 * f32::consts::PI);
+        let variant = TopplegrassVariant::choose(&self.config.variants, self.rng.gen());
+        TopplegrassSpawnSystem::spawn_topplegrass(
+            self.entities,
+            self.lazy_update,
+            self.spawn_events,
+            location,
+            wobble_phase,
+            variant,
+            self.config,
+        );
+        self.play_spawn_sound(location);
+    }
+
+    /// Sends a spawn SoundEvent at the location, unless one was sent less than
+    /// TopplegrassConfig::spawn_sound_interval seconds ago.
+    fn play_spawn_sound(&mut self, location: Vector3<f32>) {
+        if self.config.spawn_sound_volume <= 0.0
+            || *self.secs_since_spawn_sound < self.config.spawn_sound_interval
+        {
+            return;
+        }
+        *self.secs_since_spawn_sound = 0.0;
+        self.sound_events.single_write(SoundEvent {
+            kind: SoundKind::Spawn,
+            position: location,
+            volume: self.config.spawn_sound_volume,
+        });
+    }
+}
+
 impl TopplegrassSpawnSystem {
+    /// Creates a new topplegrass entity of the given variant at the given location and schedules
+    /// its prefab to be attached through a CreatureSpawnEvent. Without a variant, the topplegrass
+    /// is of the standard size.
+    fn spawn_topplegrass(
+        entities: &Entities,
+        lazy_update: &LazyUpdate,
+        spawn_events: &mut EventChannel<CreatureSpawnEvent>,
+        location: Vector3<f32>,
+        wobble_phase: f32,
+        variant: Option<usize>,
+        config: &TopplegrassConfig,
+    ) {
+        let (relative_scale, wind_susceptibility) = match variant {
+            Some(index) => (
+                config.variants[index].scale,
+                config.variants[index].wind_susceptibility,
+            ),
+            None => (1.0, 1.0),
+        };
+        let scale = TOPPLEGRASS_BASE_SCALE * relative_scale;
+        // The SpawnAnimationSystem grows the entity from zero to its intended scale.
+        let mut transform = Transform::default();
+        transform.set_scale(Vector3::<f32>::zeros());
+        transform.append_translation(location);
+        let mut builder = lazy_update
+            .create_entity(entities)
+            .with(transform)
+            .with(DragProfile {
+                area: drag_area(scale) * wind_susceptibility,
+            })
+            .with(Circle {
+                radius: TOPPLEGRASS_COLLIDER_RADIUS * relative_scale,
+            })
+            .with(SpawnVelocityTag)
+            .with(IdleTimer::default())
+            .with(GroundedTimer::default())
+            .with(Wobble::new(wobble_phase))
+            .with(SpawnAnimation::new(
+                SPAWN_ANIMATION_DURATION,
+                Vector3::new(scale, scale, scale),
+            ));
+        if let Some(index) = variant {
+            builder = builder.with(TopplegrassVariantId(index));
+        }
+        let entity = builder.build();
+        spawn_events.single_write(CreatureSpawnEvent {
+            creature_type: "Topplegrass".to_string(),
+            entity,
+        });
+    }
+
     /// Checks the time elapsed since the last spawn. If the system is ready to spawn another
     /// entity, the timer will be reset and this function will return true.
     fn ready_to_spawn(&mut self, delta_seconds: f32) -> bool {
@@ -87,40 +368,52 @@ impl TopplegrassSpawnSystem {
             false
         }
     }
+}
 
-    /// Returns a Vector3<f32> representing the position in which to spawn the next entity.
-    /// Entities will be spawned at a random point on one of the four world borders; specifically,
-    /// the one that the wind direction is facing away from. In other words: upwind from the
-    /// center of the world.
-    fn gen_spawn_location(wind: &Wind, bounds: &WorldBounds) -> Vector3<f32> {
-        let mut rng = thread_rng();
-        if Self::wind_towards_direction(wind.wind, Vector2::new(1.0, 0.0)) {
-            Vector3::new(
-                bounds.left,
-                rng.gen_range(bounds.bottom, bounds.top),
-                HEIGHT,
-            )
-        } else if Self::wind_towards_direction(wind.wind, Vector2::new(0.0, 1.0)) {
-            Vector3::new(
-                rng.gen_range(bounds.left, bounds.right),
-                bounds.bottom,
-                HEIGHT,
-            )
-        } else if Self::wind_towards_direction(wind.wind, Vector2::new(-1.0, 0.0)) {
-            Vector3::new(
-                bounds.right,
-                rng.gen_range(bounds.bottom, bounds.top),
-                HEIGHT,
-            )
-        } else {
-            Vector3::new(rng.gen_range(bounds.left, bounds.right), bounds.top, HEIGHT)
-        }
-    }
+/// Gives freshly spawned topplegrass its initial horizontal velocity: the wind velocity at its
+/// position, times TopplegrassConfig::spawn_velocity_fraction. The Movement component is added
+/// by the prefab, so this waits until the topplegrass has one.
+#[derive(Default)]
+pub struct SpawnVelocitySystem;
+
+impl<'s> System<'s> for SpawnVelocitySystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, SpawnVelocityTag>,
+        WriteStorage<'s, Movement>,
+        ReadStorage<'s, Transform>,
+        Read<'s, Wind>,
+        Read<'s, WindField>,
+        Read<'s, TopplegrassConfig>,
+    );
 
-    /// Returns true if and only if the given wind vector is roughly in line with the given
-    /// cardinal_direction vector, within a margin of a 1/4 PI RAD.
-    fn wind_towards_direction(wind: Vector2<f32>, cardinal_direction: Vector2<f32>) -> bool {
-        wind.angle(&cardinal_direction).abs() < f32::consts::FRAC_PI_4
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut spawn_velocity_tags,
+            mut movements,
+            transforms,
+            wind,
+            wind_field,
+            config,
+        ): Self::SystemData,
+    ) {
+        let launched = (&entities, &spawn_velocity_tags, &mut movements, &transforms)
+            .join()
+            .map(|(entity, _, movement, transform)| {
+                let local_wind = wind_field.sample(
+                    transform.translation().xy(),
+                    wind.at_height(transform.translation().z),
+                );
+                movement.velocity.x = local_wind.x * config.spawn_velocity_fraction;
+                movement.velocity.y = local_wind.y * config.spawn_velocity_fraction;
+                entity
+            })
+            .collect::<Vec<Entity>>();
+        for entity in launched {
+            spawn_velocity_tags.remove(entity);
+        }
     }
 }
 
@@ -138,34 +431,196 @@ impl<'s> System<'s> for TopplingSystem {
         ReadStorage<'s, TopplegrassTag>,
         WriteStorage<'s, FallingTag>,
         Read<'s, Wind>,
+        Read<'s, WindField>,
         Read<'s, Time>,
+        Read<'s, TwoDimensionalMode>,
+        Read<'s, TopplegrassConfig>,
+        ReadStorage<'s, DragProfile>,
+        Read<'s, PhysicsConfig>,
+        WriteStorage<'s, Wobble>,
+        WriteStorage<'s, RestingTag>,
+        Write<'s, GameRng>,
+        WriteStorage<'s, GroundedTimer>,
+        ReadStorage<'s, WindShadow>,
+        ReadStorage<'s, TopplegrassVariantId>,
+        ReadStorage<'s, Pinned>,
     );
 
     fn run(
         &mut self,
-        (entities, mut movements, mut transforms, topple_tags, mut falling_tags, wind, time): Self::SystemData,
+        (
+            entities,
+            mut movements,
+            mut transforms,
+            topple_tags,
+            mut falling_tags,
+            wind,
+            wind_field,
+            time,
+            two_dimensional_mode,
+            config,
+            drag_profiles,
+            physics_config,
+            mut wobbles,
+            mut resting_tags,
+            mut rng,
+            mut grounded_timers,
+            wind_shadows,
+            variant_ids,
+            pinned,
+        ): Self::SystemData,
     ) {
-        let mut rng = thread_rng();
-        // Set topplegrass velocity to equal wind velocity.
-        // Rotate topplegrass.
-        for (movement, transform, _) in (&mut movements, &mut transforms, &topple_tags).join() {
-            transform.prepend_rotation_x_axis(
-                -ANGULAR_V_MAGIC * movement.velocity.y * time.delta_seconds(),
-            );
-            transform.prepend_rotation_y_axis(
-                ANGULAR_V_MAGIC * movement.velocity.x * time.delta_seconds(),
+        // Set resting topplegrass loose again if the wind picks up enough.
+        let set_loose = (&entities, &transforms, &resting_tags)
+            .join()
+            .filter(|(_, transform, _)| {
+                wind_field
+                    .sample(
+                        transform.translation().xy(),
+                        wind.at_height(transform.translation().z),
+                    )
+                    .magnitude()
+                    > config.unfreeze_wind_speed
+            })
+            .map(|(entity, _, _)| entity)
+            .collect::<Vec<Entity>>();
+        for entity in set_loose {
+            resting_tags.remove(entity);
+        }
+        // Rotate topplegrass. Some variants roll faster or slower than others, or around other
+        // axes. Pinned topplegrass doesn't move, so it rolls along with a configured velocity.
+        let twist_phase =
+            2.0 * f32::consts::PI * config.wobble_frequency * time.absolute_time_seconds() as f32;
+        let pinned_velocity = Vector3::new(
+            config.pinned_roll_velocity.x,
+            config.pinned_roll_velocity.y,
+            0.0,
+        );
+        for (movement, transform, variant_id, pinned, _, _) in (
+            &movements,
+            &mut transforms,
+            variant_ids.maybe(),
+            pinned.maybe(),
+            &topple_tags,
+            !&resting_tags,
+        )
+            .join()
+        {
+            let velocity = if pinned.is_some() {
+                &pinned_velocity
+            } else {
+                &movement.velocity
+            };
+            let variant = variant_id.and_then(|variant_id| config.variants.get(variant_id.0));
+            let roll_speed = variant.map_or(1.0, |variant| variant.roll_speed);
+            let axes = variant
+                .and_then(|variant| variant.rotation_axes.as_ref())
+                .unwrap_or(&config.rotation_axes);
+            let (x_angle, y_angle) = roll_angles(
+                velocity,
+                config.roll_direction * roll_speed,
+                config.max_angular_speed,
+                time.delta_seconds(),
             );
-            movement.velocity.x = wind.wind.x;
-            movement.velocity.y = wind.wind.y;
+            if axes.x {
+                transform.prepend_rotation_x_axis(x_angle);
+            }
+            if axes.y {
+                transform.prepend_rotation_y_axis(y_angle);
+            }
+            if axes.z_wobble != 0.0 {
+                let twist_speed = (axes.z_wobble * twist_phase.sin())
+                    .max(-config.max_angular_speed)
+                    .min(config.max_angular_speed);
+                transform.prepend_rotation_z_axis(twist_speed * time.delta_seconds());
+            }
+        }
+        // Set topplegrass velocity to equal the wind velocity at its position, unless the
+        // WindForceSystem is responsible for dragging it along.
+        for (movement, transform, wind_shadow, _, _, _) in (
+            &mut movements,
+            &transforms,
+            wind_shadows.maybe(),
+            &topple_tags,
+            !&drag_profiles,
+            !&resting_tags,
+        )
+            .join()
+        {
+            let shelter = wind_shadow.map_or(1.0, |wind_shadow| wind_shadow.factor);
+            let local_wind = wind_field.sample(
+                transform.translation().xy(),
+                wind.at_height(transform.translation().z),
+            ) * shelter;
+            movement.velocity.x = local_wind.x;
+            movement.velocity.y = local_wind.y;
+        }
+        // Slow down topplegrass rolling over the ground.
+        if config.ground_friction > 0.0 {
+            for (movement, _, _, _) in
+                (&mut movements, &topple_tags, !&falling_tags, !&resting_tags).join()
+            {
+                apply_ground_friction(
+                    &mut movement.velocity,
+                    config.ground_friction,
+                    time.delta_seconds(),
+                );
+            }
+        }
+        // Make the topplegrass wobble from side to side. The WindForceSystem keeps the velocity
+        // of dragged topplegrass, so last frame's wobble is taken out again before applying the new
+        // one. The velocity of other topplegrass was just reset to the wind.
+        let phase_step = 2.0 * f32::consts::PI * config.wobble_frequency * time.delta_seconds();
+        for (movement, wobble, drag_profile, _, _) in (
+            &mut movements,
+            &mut wobbles,
+            drag_profiles.maybe(),
+            &topple_tags,
+            !&resting_tags,
+        )
+            .join()
+        {
+            let mut base = Vector2::new(movement.velocity.x, movement.velocity.y);
+            if drag_profile.is_some() {
+                base -= wobble.offset;
+            }
+            wobble.phase = (wobble.phase + phase_step) % (2.0 * f32::consts::PI);
+            wobble.offset = wobble_offset(base, wobble.phase, config.wobble_amplitude);
+            movement.velocity.x = base.x + wobble.offset.x;
+            movement.velocity.y = base.y + wobble.offset.y;
         }
         // Select some of the topplegrass that are on ground to jump up into the air slightly.
-        let airborne = (&entities, &mut movements, &topple_tags, !&falling_tags)
+        // The stronger the wind, the more often and the higher they jump.
+        // In two dimensional mode nothing is allowed to leave the ground plane.
+        let wind_factor = hop_wind_factor(wind.wind.magnitude(), &config);
+        let airborne = (
+            &entities,
+            &mut movements,
+            (&mut grounded_timers).maybe(),
+            &topple_tags,
+            !&falling_tags,
+            !&resting_tags,
+        )
             .join()
-            .filter_map(|(entity, movement, _, _)| {
-                if movement.velocity.magnitude() > JUMP_THRESHOLD
-                    && rng.gen::<f32>() < JUMP_PROBABILITY * time.delta_seconds()
+            .filter_map(|(entity, movement, mut grounded_timer, _, _, _)| {
+                let grounded_time = match &mut grounded_timer {
+                    Some(grounded_timer) => {
+                        grounded_timer.grounded_time += time.delta_seconds();
+                        grounded_timer.grounded_time
+                    }
+                    None => 0.0,
+                };
+                let probability =
+                    hop_probability(wind_factor) * grounded_ramp(grounded_time, &config);
+                if !two_dimensional_mode.0
+                    && movement.velocity.magnitude() > JUMP_THRESHOLD
+                    && rng.gen::<f32>() < probability * time.delta_seconds()
                 {
-                    movement.velocity.z = rng.gen_range(0.4, 0.7);
+                    take_off(movement, rng.gen::<f32>(), &config);
+                    movement.velocity.z *= wind_factor;
+                    if let Some(grounded_timer) = grounded_timer {
+                        grounded_timer.grounded_time = 0.0;
+                    }
                     Some(entity)
                 } else {
                     None
@@ -181,6 +636,9 @@ impl<'s> System<'s> for TopplingSystem {
         }
         // Check which entities are no longer falling (because they reached the ground); remove
         // their falling tag, set their vertical speed to zero (we don't bounce) and correct their position.
+        // In two dimensional mode, anything still in the air is put back on the ground immediately.
+        // If configured, topplegrass that lands slowly comes to rest.
+        let mut landed_slowly = Vec::new();
         let no_longer_falling = (
             &entities,
             &mut transforms,
@@ -190,9 +648,19 @@ impl<'s> System<'s> for TopplingSystem {
         )
             .join()
             .filter_map(|(entity, transform, movement, _, _)| {
-                if transform.translation().z <= HEIGHT && movement.velocity.z.is_sign_negative() {
-                    transform.translation_mut().z = HEIGHT;
+                if two_dimensional_mode.0
+                    || (transform.translation().z
+                        <= physics_config.ground_height + physics_config.ground_tolerance
+                        && movement.velocity.z.is_sign_negative())
+                {
+                    transform.translation_mut().z = physics_config.ground_height;
                     movement.velocity.z = 0.0;
+                    if config.freeze_on_land
+                        && movement.velocity.xy().magnitude() < config.freeze_speed_threshold
+                    {
+                        movement.velocity = Vector3::zeros();
+                        landed_slowly.push(entity);
+                    }
                     Some(entity)
                 } else {
                     None
@@ -202,5 +670,775 @@ impl<'s> System<'s> for TopplingSystem {
         for entity in no_longer_falling {
             falling_tags.remove(entity);
         }
+        for entity in landed_slowly {
+            resting_tags
+                .insert(entity, RestingTag)
+                .expect("Unable to add resting tag to entity");
+        }
+    }
+}
+
+/// Returns the cross-sectional area used for wind drag of a topplegrass with the given scale,
+/// relative to a topplegrass of the standard scale.
+pub fn drag_area(scale: f32) -> f32 {
+    (scale / TOPPLEGRASS_BASE_SCALE).powi(2)
+}
+
+/// Returns the angles in radians by which a topplegrass should rotate around its x and y axes
+/// respectively during this frame, to make it look like it is rolling along with its velocity.
+/// The angular speed around either axis is capped at max_angular_speed radians per second.
+fn roll_angles(
+    velocity: &Vector3<f32>,
+    roll_direction: f32,
+    max_angular_speed: f32,
+    delta_seconds: f32,
+) -> (f32, f32) {
+    let cap = |angular_speed: f32| angular_speed.max(-max_angular_speed).min(max_angular_speed);
+    (
+        cap(-roll_direction * ANGULAR_V_MAGIC * velocity.y) * delta_seconds,
+        cap(roll_direction * ANGULAR_V_MAGIC * velocity.x) * delta_seconds,
+    )
+}
+
+/// Returns the vertical velocity with which a topplegrass moving at the given speed should jump,
+/// clamped to the bounds set in the config.
+fn jump_impulse(speed: f32, factor: f32, config: &TopplegrassConfig) -> f32 {
+    (speed * factor)
+        .max(config.min_jump_impulse)
+        .min(config.max_jump_impulse)
+}
+
+/// Scales down the horizontal velocity of a topplegrass rolling over the ground by friction per
+/// second. The velocity never reverses, however large the friction or the frame.
+fn apply_ground_friction(velocity: &mut Vector3<f32>, friction: f32, delta_seconds: f32) {
+    let factor = (1.0 - friction * delta_seconds).max(0.0).min(1.0);
+    velocity.x *= factor;
+    velocity.y *= factor;
+}
+
+/// Returns the sideways velocity of a wobble at the given phase, perpendicular to the velocity.
+fn wobble_offset(velocity: Vector2<f32>, phase: f32, amplitude: f32) -> Vector2<f32> {
+    if velocity.magnitude() < f32::EPSILON {
+        return Vector2::zeros();
+    }
+    let sideways = Vector2::new(-velocity.y, velocity.x).normalize();
+    sideways * amplitude * phase.sin()
+}
+
+/// Returns the factor by which the wind scales the chance and height of topplegrass jumps:
+/// rising linearly from 0.0 without wind to 1.0 at TopplegrassConfig::hop_full_wind_speed.
+/// Always 1.0 if hop_full_wind_speed is not positive.
+fn hop_wind_factor(wind_speed: f32, config: &TopplegrassConfig) -> f32 {
+    if config.hop_full_wind_speed <= 0.0 {
+        1.0
+    } else {
+        (wind_speed / config.hop_full_wind_speed).max(0.0).min(1.0)
+    }
+}
+
+/// Returns the chance per second that a topplegrass on the ground jumps, given the wind factor.
+fn hop_probability(wind_factor: f32) -> f32 {
+    JUMP_PROBABILITY * wind_factor
+}
+
+/// Returns the factor by which the chance to jump is multiplied for a topplegrass that has been
+/// on the ground for grounded_time seconds. It grows linearly from 1.0 right after landing, by
+/// TopplegrassConfig::hop_ramp_rate per second, up to TopplegrassConfig::hop_ramp_max.
+fn grounded_ramp(grounded_time: f32, config: &TopplegrassConfig) -> f32 {
+    (1.0 + config.hop_ramp_rate * grounded_time)
+        .min(config.hop_ramp_max)
+        .max(1.0)
+}
+
+/// Launches a topplegrass into the air: gives it an upward velocity and boosts its horizontal
+/// velocity by the configured fraction, without exceeding its max_movement_speed.
+/// The roll, between 0.0 and 1.0, picks where in the configured ranges the jump ends up.
+fn take_off(movement: &mut Movement, roll: f32, config: &TopplegrassConfig) {
+    movement.velocity.z = if config.absolute_jump_impulse {
+        config.min_jump_impulse + (config.max_jump_impulse - config.min_jump_impulse) * roll
+    } else {
+        let factor =
+            config.min_jump_factor + (config.max_jump_factor - config.min_jump_factor) * roll;
+        jump_impulse(movement.velocity.magnitude(), factor, config)
+    };
+    let horizontal = Vector2::new(movement.velocity.x, movement.velocity.y);
+    let speed = horizontal.magnitude();
+    if speed > f32::EPSILON {
+        let boosted_speed =
+            (speed * (1.0 + config.jump_forward_boost)).min(movement.max_movement_speed.max(speed));
+        let boosted = horizontal * (boosted_speed / speed);
+        movement.velocity.x = boosted.x;
+        movement.velocity.y = boosted.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::topplegrass::RotationAxes;
+    use crate::systems::{
+        gravity::GravitySystem, movement::MovementSystem, wind_force::WindForceSystem,
+    };
+    use amethyst::ecs::{Builder, RunNow, World, WorldExt};
+
+    /// Registers the components that the TopplegrassSpawnSystem adds through the LazyUpdate, so
+    /// that spawned entities can be created when the World is maintained.
+    fn register_spawned_components(world: &mut World) {
+        world.register::<Transform>();
+        world.register::<DragProfile>();
+        world.register::<Circle>();
+        world.register::<SpawnVelocityTag>();
+        world.register::<IdleTimer>();
+        world.register::<GroundedTimer>();
+        world.register::<Wobble>();
+        world.register::<SpawnAnimation>();
+        world.register::<TopplegrassVariantId>();
+    }
+
+    fn spawned_topplegrass(sandbox: bool, spawn_commands: usize) -> usize {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        register_spawned_components(&mut world);
+        world.insert(SandboxMode(sandbox));
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        world.insert(TopplegrassConfig {
+            initial_population: 3,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        for _ in 0..spawn_commands {
+            world
+                .write_resource::<EventChannel<DebugCommand>>()
+                .single_write(DebugCommand::SpawnTopplegrass);
+        }
+        for _ in 0..100 {
+            spawner.run_now(&world);
+            world.maintain();
+        }
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        let count = spawn_events.read(&mut reader).count();
+        count
+    }
+
+    #[test]
+    fn variants_spawn_with_their_configured_scale() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        register_spawned_components(&mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        let variant = |name: &str, weight: f32, scale: f32| TopplegrassVariant {
+            name: name.to_string(),
+            weight,
+            scale,
+            wind_susceptibility: 1.0,
+            roll_speed: 1.0,
+            rotation_axes: None,
+        };
+        world.insert(TopplegrassConfig {
+            initial_population: 50,
+            variants: vec![
+                variant("small", 1.0, 0.5),
+                variant("never", 0.0, 10.0),
+                variant("large", 1.0, 2.0),
+            ],
+            ..Default::default()
+        });
+        spawner.run_now(&world);
+        world.maintain();
+
+        let variant_ids = world.read_storage::<TopplegrassVariantId>();
+        let animations = world.read_storage::<SpawnAnimation>();
+        let config = world.read_resource::<TopplegrassConfig>();
+        let mut spawned = vec![0; config.variants.len()];
+        for (variant_id, animation) in (&variant_ids, &animations).join() {
+            let expected = TOPPLEGRASS_BASE_SCALE * config.variants[variant_id.0].scale;
+            assert!((animation.target_scale.x - expected).abs() < 1e-9);
+            spawned[variant_id.0] += 1;
+        }
+        assert!(spawned[0] > 0);
+        assert_eq!(spawned[1], 0);
+        assert!(spawned[2] > 0);
+        assert!(spawned.iter().sum::<usize>() >= 50);
+    }
+
+    #[test]
+    fn sandbox_mode_only_spawns_on_command() {
+        assert!(spawned_topplegrass(false, 0) > 0);
+        assert_eq!(spawned_topplegrass(true, 0), 0);
+        assert_eq!(spawned_topplegrass(true, 2), 2);
+    }
+
+    #[test]
+    fn disabled_spawning_stops_spawns_but_not_movement() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        let mut movement = MovementSystem;
+        System::setup(&mut spawner, &mut world);
+        System::setup(&mut movement, &mut world);
+        world.register::<TopplegrassTag>();
+        world.insert(SpawningEnabled(false));
+        world.insert(TopplegrassConfig {
+            initial_population: 3,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        world
+            .write_resource::<EventChannel<DebugCommand>>()
+            .single_write(DebugCommand::SpawnTopplegrass);
+        let rolling = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::new(1.0, 0.0, 0.0),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .build();
+
+        for _ in 0..100 {
+            spawner.run_now(&world);
+            movement.run_now(&world);
+            world.maintain();
+        }
+
+        assert_eq!(
+            world
+                .fetch::<EventChannel<CreatureSpawnEvent>>()
+                .read(&mut reader)
+                .count(),
+            0
+        );
+        let transforms = world.read_storage::<Transform>();
+        assert!((transforms.get(rolling).unwrap().translation().x - 50.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn two_dimensional_mode_never_gains_vertical_velocity() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        let mut gravity = GravitySystem::default();
+        System::setup(&mut toppling, &mut world);
+        System::setup(&mut gravity, &mut world);
+        world.insert(TwoDimensionalMode(true));
+        world.insert(Wind::new(5.0, 0.0));
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(0.0, 0.0, HEIGHT);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement::default())
+            .with(TopplegrassTag)
+            .build();
+
+        for _ in 0..100 {
+            toppling.run_now(&world);
+            gravity.run_now(&world);
+            world.maintain();
+            let movements = world.read_storage::<Movement>();
+            assert_eq!(movements.get(entity).unwrap().velocity.z, 0.0);
+        }
+        assert!(world.read_storage::<FallingTag>().get(entity).is_none());
+    }
+
+    #[test]
+    fn slow_landing_topplegrass_stops_rotating() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        System::setup(&mut toppling, &mut world);
+        world.insert(TopplegrassConfig {
+            freeze_on_land: true,
+            ..Default::default()
+        });
+        world.insert(Wind::new(0.1, 0.0));
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::new(0.1, 0.0, -1.0),
+                max_movement_speed: 5.0,
+            })
+            .with(TopplegrassTag)
+            .with(FallingTag)
+            .build();
+
+        toppling.run_now(&world);
+        world.maintain();
+        assert!(world.read_storage::<RestingTag>().get(entity).is_some());
+        let rotation = *world
+            .read_storage::<Transform>()
+            .get(entity)
+            .unwrap()
+            .rotation();
+
+        for _ in 0..10 {
+            toppling.run_now(&world);
+            world.maintain();
+        }
+        let transforms = world.read_storage::<Transform>();
+        assert_eq!(*transforms.get(entity).unwrap().rotation(), rotation);
+        let movements = world.read_storage::<Movement>();
+        assert_eq!(movements.get(entity).unwrap().velocity, Vector3::zeros());
+    }
+
+    #[test]
+    fn slowly_spawned_topplegrass_is_accelerated_by_the_wind() {
+        let mut world = World::new();
+        let mut spawn_velocity = SpawnVelocitySystem::default();
+        let mut wind_force = WindForceSystem::default();
+        System::setup(&mut spawn_velocity, &mut world);
+        System::setup(&mut wind_force, &mut world);
+        world.insert(TopplegrassConfig {
+            spawn_velocity_fraction: 0.25,
+            ..Default::default()
+        });
+        world.insert(Wind::new(4.0, 0.0));
+        world.write_resource::<Time>().set_delta_seconds(0.05);
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 10.0,
+            })
+            .with(DragProfile { area: 1.0 })
+            .with(SpawnVelocityTag)
+            .build();
+
+        spawn_velocity.run_now(&world);
+        world.maintain();
+        let speed = |world: &World| {
+            world
+                .read_storage::<Movement>()
+                .get(entity)
+                .unwrap()
+                .velocity
+                .x
+        };
+        let mut previous = speed(&world);
+        assert_eq!(previous, 1.0);
+        assert!(world
+            .read_storage::<SpawnVelocityTag>()
+            .get(entity)
+            .is_none());
+        for _ in 0..10 {
+            spawn_velocity.run_now(&world);
+            wind_force.run_now(&world);
+            world.maintain();
+            let current = speed(&world);
+            assert!(current > previous && current < 4.0);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn disabled_rotation_axis_stays_unchanged() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        System::setup(&mut toppling, &mut world);
+        world.insert(TwoDimensionalMode(true));
+        world.insert(Wind::new(1.0, 1.0));
+        world.insert(TopplegrassConfig {
+            rotation_axes: RotationAxes {
+                x: true,
+                y: false,
+                z_wobble: 0.0,
+            },
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .build();
+
+        for _ in 0..3 {
+            toppling.run_now(&world);
+        }
+
+        let transforms = world.read_storage::<Transform>();
+        let (x_rotation, y_rotation, z_rotation) =
+            transforms.get(entity).unwrap().rotation().euler_angles();
+        assert!(x_rotation.abs() > 0.1);
+        assert!(y_rotation.abs() < 1e-6);
+        assert!(z_rotation.abs() < 1e-6);
+    }
+
+    #[test]
+    fn pinned_topplegrass_rolls_in_place() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        let mut movement = MovementSystem;
+        System::setup(&mut toppling, &mut world);
+        System::setup(&mut movement, &mut world);
+        world.insert(TwoDimensionalMode(true));
+        world.insert(Wind::new(3.0, 0.0));
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+        let start = Vector3::new(1.0, 2.0, 0.5);
+        let mut transform = Transform::default();
+        transform.set_translation(start);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(3.0, 0.0, 0.0),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .with(Pinned)
+            .build();
+
+        for _ in 0..3 {
+            toppling.run_now(&world);
+            movement.run_now(&world);
+        }
+
+        let transforms = world.read_storage::<Transform>();
+        let transform = transforms.get(entity).unwrap();
+        assert_eq!(*transform.translation(), start);
+        let (_, y_rotation, _) = transform.rotation().euler_angles();
+        assert!(y_rotation.abs() > 0.1);
+    }
+
+    #[test]
+    fn very_fast_topplegrass_rotates_at_most_the_max_angular_speed() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        System::setup(&mut toppling, &mut world);
+        world.insert(TwoDimensionalMode(true));
+        world.insert(Wind::new(500.0, 0.0));
+        world.insert(TopplegrassConfig {
+            max_angular_speed: 5.0,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::new(500.0, 0.0, 0.0),
+                max_movement_speed: 1000.0,
+            })
+            .with(TopplegrassTag)
+            .build();
+
+        toppling.run_now(&world);
+
+        let transforms = world.read_storage::<Transform>();
+        let angle = transforms.get(entity).unwrap().rotation().angle();
+        assert!((angle - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn flipping_roll_direction_reverses_rotation() {
+        let velocity = Vector3::new(2.0, 3.0, 0.0);
+        let (forward_x, forward_y) = roll_angles(&velocity, 1.0, f32::MAX, 0.1);
+        let (backward_x, backward_y) = roll_angles(&velocity, -1.0, f32::MAX, 0.1);
+        assert!(forward_x < 0.0 && forward_y > 0.0);
+        assert_eq!(backward_x, -forward_x);
+        assert_eq!(backward_y, -forward_y);
+    }
+
+    #[test]
+    fn rotation_is_capped_at_the_max_angular_speed() {
+        let velocity = Vector3::new(1000.0, -1000.0, 0.0);
+        let (x_angle, y_angle) = roll_angles(&velocity, 1.0, 10.0, 0.1);
+        assert!((x_angle - 1.0).abs() < 1e-6);
+        assert!((y_angle - 1.0).abs() < 1e-6);
+
+        let slow = Vector3::new(1.0, 0.0, 0.0);
+        let (_, slow_y_angle) = roll_angles(&slow, 1.0, 10.0, 0.1);
+        assert!((slow_y_angle - ANGULAR_V_MAGIC * 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stronger_wind_makes_topplegrass_hop_more_often() {
+        let config = TopplegrassConfig {
+            hop_full_wind_speed: 4.0,
+            ..Default::default()
+        };
+        let calm = hop_probability(hop_wind_factor(0.0, &config));
+        let breeze = hop_probability(hop_wind_factor(1.0, &config));
+        let storm = hop_probability(hop_wind_factor(10.0, &config));
+        assert_eq!(calm, 0.0);
+        assert!(storm > breeze && breeze > calm);
+        assert_eq!(storm, JUMP_PROBABILITY);
+    }
+
+    #[test]
+    fn long_grounded_topplegrass_is_more_likely_to_hop() {
+        let config = TopplegrassConfig {
+            hop_ramp_rate: 0.5,
+            hop_ramp_max: 3.0,
+            ..Default::default()
+        };
+        let just_landed = hop_probability(1.0) * grounded_ramp(0.1, &config);
+        let long_grounded = hop_probability(1.0) * grounded_ramp(2.0, &config);
+        assert!(long_grounded > just_landed);
+        assert_eq!(grounded_ramp(0.0, &config), 1.0);
+        assert_eq!(grounded_ramp(100.0, &config), 3.0);
+    }
+
+    #[test]
+    fn jump_impulse_respects_configured_bounds() {
+        let config = TopplegrassConfig::default();
+        assert_eq!(
+            jump_impulse(0.1, config.min_jump_factor, &config),
+            config.min_jump_impulse
+        );
+        assert_eq!(
+            jump_impulse(100.0, config.max_jump_factor, &config),
+            config.max_jump_impulse
+        );
+        let impulse = jump_impulse(2.0, 0.3, &config);
+        assert!((impulse - 0.6).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn initial_burst_spawns_configured_population_once() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        world.insert(TopplegrassConfig {
+            initial_population: 7,
+            ..Default::default()
+        });
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        spawner.run_now(&world);
+        spawner.run_now(&world);
+
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        assert_eq!(spawn_events.read(&mut reader).count(), 7);
+    }
+
+    #[test]
+    fn spawning_plays_a_sound_at_the_spawn_location() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        register_spawned_components(&mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        world.insert(TopplegrassConfig {
+            initial_population: 3,
+            ..Default::default()
+        });
+        let mut sound_reader = world
+            .fetch_mut::<EventChannel<SoundEvent>>()
+            .register_reader();
+
+        spawner.run_now(&world);
+        world.maintain();
+
+        let sound_events = world.fetch::<EventChannel<SoundEvent>>();
+        let sounds = sound_events
+            .read(&mut sound_reader)
+            .cloned()
+            .collect::<Vec<SoundEvent>>();
+        // The burst of three spawns only plays a single sound.
+        assert_eq!(sounds.len(), 1);
+        assert_eq!(sounds[0].kind, SoundKind::Spawn);
+        let transforms = world.read_storage::<Transform>();
+        let spawn_velocity_tags = world.read_storage::<SpawnVelocityTag>();
+        assert!((&transforms, &spawn_velocity_tags)
+            .join()
+            .any(|(transform, _)| *transform.translation() == sounds[0].position));
+    }
+
+    #[test]
+    fn edge_spawn_presses_go_around_the_borders() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        register_spawned_components(&mut world);
+        world.insert(SandboxMode(true));
+        world.insert(WorldBounds::new(-10.0, 10.0, -4.0, 4.0));
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        for _ in 0..4 {
+            world
+                .write_resource::<EventChannel<DebugCommand>>()
+                .single_write(DebugCommand::SpawnTopplegrassAtEdge);
+            spawner.run_now(&world);
+        }
+        world.maintain();
+
+        let spawned = world
+            .fetch::<EventChannel<CreatureSpawnEvent>>()
+            .read(&mut reader)
+            .map(|event| event.entity)
+            .collect::<Vec<Entity>>();
+        let transforms = world.read_storage::<Transform>();
+        let locations = spawned
+            .iter()
+            .map(|entity| transforms.get(*entity).unwrap().translation().xy())
+            .collect::<Vec<Vector2<f32>>>();
+        assert_eq!(
+            locations,
+            vec![
+                Vector2::new(-10.0, 0.0),
+                Vector2::new(0.0, -4.0),
+                Vector2::new(10.0, 0.0),
+                Vector2::new(0.0, 4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn reset_spawns_the_initial_population_again() {
+        let mut world = World::new();
+        let mut spawner = TopplegrassSpawnSystem::default();
+        System::setup(&mut spawner, &mut world);
+        world.insert(WorldBounds::new(-10.0, 10.0, -10.0, 10.0));
+        world.insert(TopplegrassConfig {
+            initial_population: 4,
+            ..Default::default()
+        });
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        spawner.run_now(&world);
+        world
+            .write_resource::<EventChannel<DebugCommand>>()
+            .single_write(DebugCommand::ResetSim);
+        spawner.run_now(&world);
+        spawner.run_now(&world);
+
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        assert_eq!(spawn_events.read(&mut reader).count(), 8);
+    }
+
+    #[test]
+    fn take_off_adds_configured_vertical_velocity_and_boost() {
+        let config = TopplegrassConfig {
+            min_jump_impulse: 1.0,
+            max_jump_impulse: 2.0,
+            absolute_jump_impulse: true,
+            jump_forward_boost: 0.5,
+            ..Default::default()
+        };
+        let mut movement = Movement {
+            velocity: Vector3::new(2.0, 0.0, 0.0),
+            max_movement_speed: 10.0,
+        };
+        take_off(&mut movement, 0.5, &config);
+        assert!((movement.velocity - Vector3::new(3.0, 0.0, 1.5)).magnitude() < 1e-6);
+
+        let mut fast = Movement {
+            velocity: Vector3::new(0.0, 4.0, 0.0),
+            max_movement_speed: 5.0,
+        };
+        take_off(&mut fast, 0.0, &config);
+        assert!((fast.velocity - Vector3::new(0.0, 5.0, 1.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn ground_friction_slows_down_grass_without_wind() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        System::setup(&mut toppling, &mut world);
+        world.insert(Wind::new(0.0, 0.0));
+        world.insert(TopplegrassConfig {
+            ground_friction: 1.0,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.1);
+
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::new(2.0, 1.0, 0.0),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .with(DragProfile { area: 1.0 })
+            .build();
+
+        let speed = |world: &World| {
+            world
+                .read_storage::<Movement>()
+                .get(entity)
+                .unwrap()
+                .velocity
+                .magnitude()
+        };
+        let mut previous_speed = speed(&world);
+        for _ in 0..50 {
+            toppling.run_now(&world);
+            let current_speed = speed(&world);
+            assert!(current_speed < previous_speed);
+            previous_speed = current_speed;
+        }
+        assert!(previous_speed < 0.05);
+
+        // A huge friction stops the grass, but doesn't send it backwards.
+        let mut velocity = Vector3::new(2.0, 1.0, 0.0);
+        apply_ground_friction(&mut velocity, 100.0, 0.1);
+        assert_eq!(velocity, Vector3::zeros());
+    }
+
+    #[test]
+    fn wobbling_topplegrass_deviates_from_a_straight_line() {
+        let mut world = World::new();
+        let mut toppling = TopplingSystem::default();
+        let mut movement_system = MovementSystem;
+        System::setup(&mut toppling, &mut world);
+        System::setup(&mut movement_system, &mut world);
+        register_spawned_components(&mut world);
+        world.insert(TwoDimensionalMode(true));
+        world.insert(Wind::new(5.0, 0.0));
+        world.insert(TopplegrassConfig {
+            wobble_amplitude: 1.0,
+            wobble_frequency: 1.0,
+            ..Default::default()
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.05);
+
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .with(SpawnVelocityTag)
+            .with(IdleTimer::default())
+            .with(GroundedTimer::default())
+            .with(Wobble::new(0.0))
+            .build();
+
+        for _ in 0..5 {
+            toppling.run_now(&world);
+            movement_system.run_now(&world);
+        }
+
+        let transforms = world.read_storage::<Transform>();
+        let translation = transforms.get(entity).unwrap().translation();
+        assert!(translation.x > 0.0);
+        assert!(translation.y.abs() > 0.01);
     }
 }
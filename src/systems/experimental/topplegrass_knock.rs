@@ -0,0 +1,150 @@
+use amethyst::{
+    ecs::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::creatures::{CreatureTag, Movement, TopplegrassTag},
+    resources::topplegrass::TopplegrassConfig,
+    systems::collision::CollisionEvent,
+};
+
+use std::collections::HashSet;
+
+/// Lets creatures kick topplegrass they run into. A fraction of the velocity the creature hit
+/// the topplegrass with, TopplegrassConfig::knock_coefficient, is added to the velocity of the
+/// topplegrass, so it flies off in the direction the creature was moving. The topplegrass still
+/// doesn't move faster than its max_movement_speed.
+/// A creature only kicks a topplegrass when it runs into it, not on every frame they keep touching.
+#[derive(Default)]
+pub struct TopplegrassKnockSystem {
+    collision_reader_id: Option<ReaderId<CollisionEvent>>,
+    /// The creatures and the topplegrass they touched during the previous run.
+    contacts: HashSet<(Entity, Entity)>,
+}
+
+impl<'s> System<'s> for TopplegrassKnockSystem {
+    type SystemData = (
+        Read<'s, EventChannel<CollisionEvent>>,
+        ReadStorage<'s, CreatureTag>,
+        ReadStorage<'s, TopplegrassTag>,
+        WriteStorage<'s, Movement>,
+        Read<'s, TopplegrassConfig>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.collision_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(
+        &mut self,
+        (collision_events, creature_tags, topple_tags, mut movements, config): Self::SystemData,
+    ) {
+        let mut contacts = HashSet::new();
+        for event in collision_events.read(self.collision_reader_id.as_mut().unwrap()) {
+            let (creature, grass) = (event.entity_a, event.entity_b);
+            if !creature_tags.contains(creature)
+                || topple_tags.contains(creature)
+                || !topple_tags.contains(grass)
+            {
+                continue;
+            }
+            let new_contact =
+                contacts.insert((creature, grass)) && !self.contacts.contains(&(creature, grass));
+            if !new_contact {
+                continue;
+            }
+            if let Some(movement) = movements.get_mut(grass) {
+                movement.velocity += event.impact_velocity * config.knock_coefficient;
+                let speed = movement.velocity.magnitude();
+                if speed > movement.max_movement_speed {
+                    movement.velocity *= movement.max_movement_speed / speed;
+                }
+            }
+        }
+        self.contacts = contacts;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector3;
+
+    #[test]
+    fn creature_kicks_still_topplegrass_along() {
+        let mut world = World::new();
+        let mut system = TopplegrassKnockSystem::default();
+        System::setup(&mut system, &mut world);
+        let creature = world.create_entity().with(CreatureTag).build();
+        let grass = world
+            .create_entity()
+            .with(TopplegrassTag)
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 1.0,
+            })
+            .build();
+        let mut kick = |velocity: Vector3<f32>| {
+            world
+                .write_resource::<EventChannel<CollisionEvent>>()
+                .single_write(CollisionEvent::new(creature, grass, velocity));
+            system.run_now(&world);
+            // Step apart again, so the next kick is a new contact.
+            system.run_now(&world);
+            world
+                .read_storage::<Movement>()
+                .get(grass)
+                .unwrap()
+                .velocity
+        };
+
+        // Knocked in the direction the creature moves, by half its speed.
+        let velocity = kick(Vector3::new(0.0, 1.2, 0.0));
+        assert!((velocity - Vector3::new(0.0, 0.6, 0.0)).magnitude() < 1e-6);
+
+        // A hard kick is capped at the max movement speed of the topplegrass.
+        let velocity = kick(Vector3::new(0.0, 10.0, 0.0));
+        assert!((velocity.magnitude() - 1.0).abs() < 1e-6);
+        assert!(velocity.y > 0.0);
+    }
+
+    #[test]
+    fn staying_in_contact_kicks_only_once() {
+        let mut world = World::new();
+        let mut system = TopplegrassKnockSystem::default();
+        System::setup(&mut system, &mut world);
+        let creature = world.create_entity().with(CreatureTag).build();
+        let grass = world
+            .create_entity()
+            .with(TopplegrassTag)
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 10.0,
+            })
+            .build();
+
+        for _ in 0..5 {
+            world
+                .write_resource::<EventChannel<CollisionEvent>>()
+                .single_write(CollisionEvent::new(
+                    creature,
+                    grass,
+                    Vector3::new(1.0, 0.0, 0.0),
+                ));
+            system.run_now(&world);
+        }
+
+        let velocity = world
+            .read_storage::<Movement>()
+            .get(grass)
+            .unwrap()
+            .velocity;
+        assert!((velocity - Vector3::new(0.5, 0.0, 0.0)).magnitude() < 1e-6);
+    }
+}
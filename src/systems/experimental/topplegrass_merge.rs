@@ -0,0 +1,263 @@
+use amethyst::{
+    core::{math::Vector3, transform::Transform},
+    ecs::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::{
+        collider::Circle,
+        creatures::{DragProfile, Movement, TopplegrassTag},
+        spawn_animation::SpawnAnimation,
+    },
+    resources::topplegrass::TopplegrassConfig,
+    systems::{
+        collision::CollisionEvent,
+        spawner::CreatureSpawnEvent,
+        topplegrass::{drag_area, TOPPLEGRASS_BASE_SCALE, TOPPLEGRASS_COLLIDER_RADIUS},
+    },
+};
+
+/// Makes topplegrass that collide at a similar speed tangle together into one bigger topplegrass.
+/// Both are deleted, and a new topplegrass is spawned at their midpoint, with the sum of their
+/// volumes. The new topplegrass gets its prefab through a CreatureSpawnEvent; once the prefab
+/// has given it a Movement, it continues with the average velocity of the two.
+/// Speeds are compared rather than velocities, because the CollisionSystem has already deflected
+/// the colliding entities by the time this system sees the collision.
+#[derive(Default)]
+pub struct TopplegrassMergeSystem {
+    collision_reader_id: Option<ReaderId<CollisionEvent>>,
+    /// Merged topplegrass waiting for their prefab, with the velocity to continue with.
+    pending_velocities: Vec<(Entity, Vector3<f32>)>,
+}
+
+impl<'s> System<'s> for TopplegrassMergeSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        Read<'s, EventChannel<CollisionEvent>>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        ReadStorage<'s, TopplegrassTag>,
+        ReadStorage<'s, SpawnAnimation>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Movement>,
+        Read<'s, TopplegrassConfig>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.collision_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CollisionEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            lazy_update,
+            collision_events,
+            mut spawn_events,
+            topplegrass_tags,
+            spawn_animations,
+            transforms,
+            mut movements,
+            config,
+        ): Self::SystemData,
+    ) {
+        self.pending_velocities.retain(|(entity, velocity)| {
+            if !entities.is_alive(*entity) {
+                return false;
+            }
+            match movements.get_mut(*entity) {
+                Some(movement) => {
+                    movement.velocity = *velocity;
+                    false
+                }
+                None => true,
+            }
+        });
+
+        let mut merged = BitSet::new();
+        for event in collision_events.read(self.collision_reader_id.as_mut().unwrap()) {
+            let (a, b) = (event.entity_a, event.entity_b);
+            if merged.contains(a.id())
+                || merged.contains(b.id())
+                || !topplegrass_tags.contains(a)
+                || !topplegrass_tags.contains(b)
+                || spawn_animations.contains(a)
+                || spawn_animations.contains(b)
+            {
+                continue;
+            }
+            let (velocity_a, velocity_b) = match (movements.get(a), movements.get(b)) {
+                (Some(a), Some(b)) => (a.velocity, b.velocity),
+                _ => continue,
+            };
+            if (velocity_a.magnitude() - velocity_b.magnitude()).abs()
+                >= config.merge_speed_threshold
+            {
+                continue;
+            }
+            let (position_a, scale_a, position_b, scale_b) =
+                match (transforms.get(a), transforms.get(b)) {
+                    (Some(a), Some(b)) => {
+                        (*a.translation(), a.scale().x, *b.translation(), b.scale().x)
+                    }
+                    _ => continue,
+                };
+            let new_scale = merged_scale(scale_a, scale_b);
+            if new_scale > TOPPLEGRASS_BASE_SCALE * config.max_merge_scale {
+                continue;
+            }
+
+            merged.add(a.id());
+            merged.add(b.id());
+            let _ = entities.delete(a);
+            let _ = entities.delete(b);
+            let mut transform = Transform::default();
+            transform.set_translation((position_a + position_b) / 2.0);
+            transform.set_scale(Vector3::new(new_scale, new_scale, new_scale));
+            let entity = lazy_update
+                .create_entity(&entities)
+                .with(transform)
+                .with(DragProfile {
+                    area: drag_area(new_scale),
+                })
+                .with(Circle {
+                    radius: TOPPLEGRASS_COLLIDER_RADIUS * new_scale / TOPPLEGRASS_BASE_SCALE,
+                })
+                .build();
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Topplegrass".to_string(),
+                entity,
+            });
+            self.pending_velocities
+                .push((entity, (velocity_a + velocity_b) / 2.0));
+        }
+    }
+}
+
+/// Returns the scale of a topplegrass with the combined volume of two topplegrass of the given
+/// scales.
+fn merged_scale(scale: f32, other_scale: f32) -> f32 {
+    (scale.powi(3) + other_scale.powi(3)).cbrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge_world() -> (World, TopplegrassMergeSystem) {
+        let mut world = World::new();
+        let mut system = TopplegrassMergeSystem::default();
+        System::setup(&mut system, &mut world);
+        world.register::<DragProfile>();
+        world.register::<Circle>();
+        (world, system)
+    }
+
+    fn topplegrass(world: &mut World, x: f32, speed: f32, scale: f32) -> Entity {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(x, 0.0, 0.5);
+        transform.set_scale(Vector3::new(scale, scale, scale));
+        world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(speed, 0.0, 0.0),
+                max_movement_speed: 10.0,
+            })
+            .with(TopplegrassTag)
+            .build()
+    }
+
+    fn collide(world: &mut World, system: &mut TopplegrassMergeSystem, a: Entity, b: Entity) {
+        world
+            .fetch_mut::<EventChannel<CollisionEvent>>()
+            .single_write(CollisionEvent::new(a, b, Vector3::new(1.0, 0.0, 0.0)));
+        system.run_now(world);
+        world.maintain();
+    }
+
+    #[test]
+    fn two_equal_topplegrass_merge_into_a_bigger_one() {
+        let (mut world, mut system) = merge_world();
+        let mut spawn_reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        let a = topplegrass(&mut world, 1.0, 1.0, TOPPLEGRASS_BASE_SCALE);
+        let b = topplegrass(&mut world, 2.0, 1.2, TOPPLEGRASS_BASE_SCALE);
+
+        collide(&mut world, &mut system, a, b);
+
+        assert!(!world.is_alive(a));
+        assert!(!world.is_alive(b));
+        let spawned = world
+            .fetch::<EventChannel<CreatureSpawnEvent>>()
+            .read(&mut spawn_reader)
+            .map(|event| event.entity)
+            .collect::<Vec<Entity>>();
+        assert_eq!(spawned.len(), 1);
+        let merged = spawned[0];
+        {
+            let transforms = world.read_storage::<Transform>();
+            let transform = transforms.get(merged).unwrap();
+            let expected_scale = TOPPLEGRASS_BASE_SCALE * 2f32.cbrt();
+            assert!((transform.scale().x - expected_scale).abs() < 1e-7);
+            assert!((transform.translation().x - 1.5).abs() < 1e-6);
+        }
+
+        // Stand in for the Movement the prefab would add.
+        world
+            .write_storage::<Movement>()
+            .insert(
+                merged,
+                Movement {
+                    velocity: Vector3::zeros(),
+                    max_movement_speed: 10.0,
+                },
+            )
+            .unwrap();
+        system.run_now(&world);
+        let velocity = world
+            .read_storage::<Movement>()
+            .get(merged)
+            .unwrap()
+            .velocity;
+        assert!((velocity - Vector3::new(1.1, 0.0, 0.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn topplegrass_colliding_at_different_speeds_stay_apart() {
+        let (mut world, mut system) = merge_world();
+        let a = topplegrass(&mut world, 1.0, 1.0, TOPPLEGRASS_BASE_SCALE);
+        let b = topplegrass(&mut world, 2.0, 2.0, TOPPLEGRASS_BASE_SCALE);
+
+        collide(&mut world, &mut system, a, b);
+
+        assert!(world.is_alive(a));
+        assert!(world.is_alive(b));
+        assert_eq!(world.read_storage::<DragProfile>().join().count(), 0);
+    }
+
+    #[test]
+    fn topplegrass_never_merge_beyond_the_max_merge_scale() {
+        let (mut world, mut system) = merge_world();
+        // Together they would be more than 2.5 times the standard scale.
+        let a = topplegrass(&mut world, 1.0, 1.0, TOPPLEGRASS_BASE_SCALE * 2.0);
+        let b = topplegrass(&mut world, 2.0, 1.0, TOPPLEGRASS_BASE_SCALE * 2.0);
+        world.insert(TopplegrassConfig {
+            max_merge_scale: 2.5,
+            ..Default::default()
+        });
+
+        collide(&mut world, &mut system, a, b);
+
+        assert!(world.is_alive(a));
+        assert!(world.is_alive(b));
+        assert_eq!(world.read_storage::<DragProfile>().join().count(), 0);
+    }
+}
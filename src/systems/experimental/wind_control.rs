@@ -2,34 +2,81 @@ use amethyst::{
     core::{math::Vector2, timing::Time},
     ecs::*,
     input::{InputHandler, StringBindings},
+    shrev::{EventChannel, ReaderId},
 };
 
-use crate::resources::wind::Wind;
+use crate::{
+    resources::wind::{Wind, WindLoggingEnabled, WindSpeedContributions},
+    systems::debug_input::DebugCommand,
+};
 use std::f32;
 
 /// Wind speed cannot decrease below this number.
 const MIN_WIND_SPEED: f32 = 0.0;
 /// Wind speed cannot increase above this number.
 const MAX_WIND_SPEED: f32 = 5.0;
-/// Speed with which to rotate wind speed in radians per second.
+/// Default speed with which to rotate wind speed in radians per second.
 const WIND_TURN_SPEED: f32 = f32::consts::FRAC_PI_4;
 /// Speed with which to increase or decrease wind speed in meters?? per second per second.
 const WIND_ACCELERATION: f32 = 2.0;
+/// Angle in radians by which to rotate the wind for each press of a wind rotation action.
+const WIND_ROTATION_STEP: f32 = f32::consts::FRAC_PI_8;
 
 /// DebugWindControlSystem allows players to change the wind speed and direction at runtime.
-/// Use the ChangeWindDirection input axis to change the wind direction at WIND_TURN_SPEED radians per second.
-/// Use the ChangeWindSpeed input axis to change the wind speed between MIN_WIND_SPEED and MAX_WIND_SPEED.
-#[derive(Default)]
-pub struct DebugWindControlSystem;
+/// Use the ChangeWindDirection input axis to change the wind direction at turn_speed radians per second.
+/// Use the ChangeWindSpeed input axis to change the base wind speed between MIN_WIND_SPEED and
+/// MAX_WIND_SPEED. The WindMagnitudeControllerSystem then moves the actual wind speed towards it.
+/// The RotateWindCounterClockwise and RotateWindClockwise debug commands rotate the wind by
+/// WIND_ROTATION_STEP radians each.
+/// The ToggleWindLogging debug command toggles whether wind changes are logged.
+pub struct DebugWindControlSystem {
+    turn_speed: f32,
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+}
+
+impl DebugWindControlSystem {
+    /// Creates a DebugWindControlSystem that rotates the wind at the given speed in radians per
+    /// second, for finer or coarser control than the default WIND_TURN_SPEED.
+    pub fn new(turn_speed: f32) -> DebugWindControlSystem {
+        DebugWindControlSystem {
+            turn_speed,
+            command_reader_id: None,
+        }
+    }
+}
+
+impl Default for DebugWindControlSystem {
+    fn default() -> Self {
+        DebugWindControlSystem::new(WIND_TURN_SPEED)
+    }
+}
 
 impl<'s> System<'s> for DebugWindControlSystem {
     type SystemData = (
         Read<'s, InputHandler<StringBindings>>,
         Write<'s, Wind>,
         Read<'s, Time>,
+        Read<'s, EventChannel<DebugCommand>>,
+        Write<'s, WindLoggingEnabled>,
+        Write<'s, WindSpeedContributions>,
     );
 
-    fn run(&mut self, (input, mut wind, time): Self::SystemData) {
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(
+        &mut self,
+        (input, mut wind, time, debug_commands, mut logging_enabled, mut speed_contributions): Self::SystemData,
+    ) {
+        for command in debug_commands.read(self.command_reader_id.as_mut().unwrap()) {
+            handle_command(*command, &mut wind, &mut logging_enabled);
+        }
         let change_direction = input
             .axis_value("ChangeWindDirection")
             .filter(|signum| signum.abs() > std::f32::EPSILON);
@@ -39,32 +86,181 @@ impl<'s> System<'s> for DebugWindControlSystem {
         if change_direction.is_none() && change_speed.is_none() {
             return;
         }
-        let new_angle = calc_wind_angle(change_direction, &wind, &time);
-        let new_speed = calc_wind_speed(change_speed, &wind, &time);
-        wind.wind = Vector2::new(new_speed * new_angle.cos(), new_speed * new_angle.sin());
-        println!(
-            "Changed wind vector to: ({:?},{:?}) angle={:?} speed={:?}",
-            wind.wind.x, wind.wind.y, new_angle, new_speed
+        let new_angle = calc_wind_angle(change_direction, self.turn_speed, &wind, &time);
+        let new_speed = calc_wind_speed(change_speed, speed_contributions.base, &time);
+        speed_contributions.base = new_speed;
+        wind.wind = rotate_vector(Vector2::new(wind.wind.magnitude(), 0.0), new_angle);
+        if logging_enabled.0 {
+            info!(
+                "Changed wind vector to: ({:?},{:?}) angle={:?} speed={:?}",
+                wind.wind.x, wind.wind.y, new_angle, new_speed
+            );
+        }
+    }
+}
+
+/// Handles the wind related debug commands: stepwise rotation of the wind and toggling whether
+/// wind changes are logged.
+fn handle_command(
+    command: DebugCommand,
+    wind: &mut Wind,
+    logging_enabled: &mut WindLoggingEnabled,
+) {
+    let sign = match command {
+        DebugCommand::RotateWindCounterClockwise => 1.0,
+        DebugCommand::RotateWindClockwise => -1.0,
+        DebugCommand::ToggleWindLogging => {
+            logging_enabled.0 = !logging_enabled.0;
+            return;
+        }
+        _ => return,
+    };
+    wind.wind = step_wind_direction(wind.wind, sign, WIND_ROTATION_STEP);
+    if logging_enabled.0 {
+        info!(
+            "Changed wind vector to: ({:?},{:?})",
+            wind.wind.x, wind.wind.y
         );
     }
 }
 
-fn calc_wind_angle(input_signum: Option<f32>, wind: &Wind, time: &Time) -> f32 {
+/// Rotates the given vector counter-clockwise by the given angle in radians.
+/// The magnitude of the vector is preserved.
+pub fn rotate_vector(v: Vector2<f32>, by_rad: f32) -> Vector2<f32> {
+    let (sin, cos) = by_rad.sin_cos();
+    Vector2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+/// Rotates the wind by a single step. A positive sign rotates counter-clockwise, a negative
+/// sign rotates clockwise.
+fn step_wind_direction(wind: Vector2<f32>, sign: f32, step: f32) -> Vector2<f32> {
+    rotate_vector(wind, sign * step)
+}
+
+fn calc_wind_angle(input_signum: Option<f32>, turn_speed: f32, wind: &Wind, time: &Time) -> f32 {
     let old_wind_angle = wind.wind.y.atan2(wind.wind.x);
     if let Some(signum) = input_signum {
-        old_wind_angle + signum * WIND_TURN_SPEED * time.delta_seconds()
+        old_wind_angle + signum * turn_speed * time.delta_seconds()
     } else {
         old_wind_angle
     }
 }
 
-fn calc_wind_speed(input_signum: Option<f32>, wind: &Wind, time: &Time) -> f32 {
-    let magnitude = wind.wind.magnitude();
+fn calc_wind_speed(input_signum: Option<f32>, base_speed: f32, time: &Time) -> f32 {
     if let Some(signum) = input_signum {
-        (magnitude + signum * WIND_ACCELERATION * time.delta_seconds())
+        (base_speed + signum * WIND_ACCELERATION * time.delta_seconds())
             .max(MIN_WIND_SPEED)
             .min(MAX_WIND_SPEED)
     } else {
-        magnitude
+        base_speed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_turn_speed_rotates_wind_by_that_amount() {
+        let wind = Wind::new(1.0, 0.0);
+        let mut time = Time::default();
+        time.set_delta_seconds(1.0);
+        let angle = calc_wind_angle(Some(1.0), f32::consts::FRAC_PI_4, &wind, &time);
+        assert!((angle - f32::consts::FRAC_PI_4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rotating_a_quarter_turn() {
+        let rotated = rotate_vector(Vector2::new(1.0, 0.0), f32::consts::FRAC_PI_2);
+        assert!((rotated - Vector2::new(0.0, 1.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn rotating_a_half_turn() {
+        let rotated = rotate_vector(Vector2::new(1.0, 0.0), f32::consts::PI);
+        assert!((rotated - Vector2::new(-1.0, 0.0)).magnitude() < 1e-6);
+    }
+
+    #[test]
+    fn rotating_preserves_magnitude() {
+        let v = Vector2::new(3.0, -4.0);
+        let rotated = rotate_vector(v, 1.234);
+        assert!((rotated.magnitude() - v.magnitude()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn clockwise_step_decreases_wind_angle() {
+        let wind = Vector2::new(1.0, 1.0);
+        let rotated = step_wind_direction(wind, -1.0, WIND_ROTATION_STEP);
+        let old_angle = wind.y.atan2(wind.x);
+        let new_angle = rotated.y.atan2(rotated.x);
+        assert!((old_angle - new_angle - WIND_ROTATION_STEP).abs() < 1e-6);
+    }
+
+    mod capture {
+        use log::{LevelFilter, Log, Metadata, Record};
+        use std::cell::RefCell;
+
+        thread_local! {
+            static MESSAGES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+        }
+
+        /// Collects the messages of the log records emitted from the wind_control module. Tests
+        /// run on threads of their own, so every test only sees the records of its own thread.
+        pub struct CaptureLogger;
+
+        impl Log for CaptureLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                if record.target().ends_with("wind_control") {
+                    MESSAGES
+                        .with(|messages| messages.borrow_mut().push(format!("{}", record.args())));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CaptureLogger = CaptureLogger;
+
+        pub fn install() {
+            let _ = log::set_logger(&LOGGER);
+            log::set_max_level(LevelFilter::Trace);
+        }
+
+        /// Removes and returns the messages captured on the current thread.
+        pub fn take_messages() -> Vec<String> {
+            MESSAGES.with(|messages| messages.borrow_mut().drain(..).collect())
+        }
+    }
+
+    #[test]
+    fn disabled_logging_changes_wind_silently() {
+        capture::install();
+        let mut world = World::new();
+        let mut system = DebugWindControlSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(Wind::new(1.0, 0.0));
+        let rotate = |world: &mut World, system: &mut DebugWindControlSystem| {
+            world
+                .write_resource::<EventChannel<DebugCommand>>()
+                .single_write(DebugCommand::RotateWindClockwise);
+            system.run_now(world);
+        };
+
+        // Make sure the logger captures wind changes, so the silence below means something.
+        world.insert(WindLoggingEnabled(true));
+        rotate(&mut world, &mut system);
+        assert_eq!(capture::take_messages().len(), 1);
+
+        world.insert(WindLoggingEnabled(false));
+        let before = world.read_resource::<Wind>().wind;
+        rotate(&mut world, &mut system);
+
+        assert!((world.read_resource::<Wind>().wind - before).magnitude() > 0.1);
+        assert!(capture::take_messages().is_empty());
     }
 }
@@ -0,0 +1,101 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time, transform::Transform},
+    ecs::*,
+};
+
+use crate::{
+    components::{
+        creatures::{DragProfile, Movement, RestingTag},
+        wind_shadow::WindShadow,
+    },
+    resources::{wind::Wind, wind_field::WindField},
+};
+
+/// How strongly the wind drags along an entity, per unit of cross-sectional area per second.
+const DRAG_COEFFICIENT: f32 = 4.0;
+
+/// Applies a drag force from the wind to every entity with a DragProfile that isn't resting, pulling its horizontal
+/// velocity towards the wind velocity. The force is proportional to the entity's cross-sectional
+/// area and the difference between the wind at its position and the entity's own velocity.
+#[derive(Default)]
+pub struct WindForceSystem;
+
+impl<'s> System<'s> for WindForceSystem {
+    type SystemData = (
+        WriteStorage<'s, Movement>,
+        ReadStorage<'s, DragProfile>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, RestingTag>,
+        ReadStorage<'s, WindShadow>,
+        Read<'s, Wind>,
+        Read<'s, WindField>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut movements,
+            drag_profiles,
+            transforms,
+            resting_tags,
+            wind_shadows,
+            wind,
+            wind_field,
+            time,
+        ): Self::SystemData,
+    ) {
+        for (movement, drag_profile, transform, wind_shadow, _) in (
+            &mut movements,
+            &drag_profiles,
+            &transforms,
+            wind_shadows.maybe(),
+            !&resting_tags,
+        )
+            .join()
+        {
+            let shelter = wind_shadow.map_or(1.0, |wind_shadow| wind_shadow.factor);
+            let local_wind = wind_field.sample(
+                transform.translation().xy(),
+                wind.at_height(transform.translation().z),
+            ) * shelter;
+            let velocity = apply_drag(
+                Vector2::new(movement.velocity.x, movement.velocity.y),
+                local_wind,
+                drag_profile.area,
+                time.delta_seconds(),
+            );
+            movement.velocity.x = velocity.x;
+            movement.velocity.y = velocity.y;
+        }
+    }
+}
+
+/// Returns the new horizontal velocity of an entity after being dragged along by the wind for
+/// delta_seconds. The entity never overshoots the wind velocity, even for very large deltas.
+fn apply_drag(
+    velocity: Vector2<f32>,
+    wind: Vector2<f32>,
+    area: f32,
+    delta_seconds: f32,
+) -> Vector2<f32> {
+    let blend = (DRAG_COEFFICIENT * area * delta_seconds).max(0.0).min(1.0);
+    velocity + (wind - velocity) * blend
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn larger_area_converges_to_wind_faster() {
+        let wind = Vector2::new(3.0, 0.0);
+        let mut small = Vector2::zeros();
+        let mut large = Vector2::zeros();
+        for _ in 0..10 {
+            small = apply_drag(small, wind, 0.5, 0.02);
+            large = apply_drag(large, wind, 2.0, 0.02);
+            assert!((wind - large).magnitude() < (wind - small).magnitude());
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use amethyst::{ecs::*, ui::*};
+
+use std::f32::consts::PI;
+
+use crate::resources::wind::Wind;
+
+/// Id of the label in the main game UI prefab that shows the wind.
+const WIND_HUD_ID: &str = "wind hud";
+
+/// Compass headings, clockwise from north.
+const COMPASS_LABELS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// Keeps the wind label of the main game UI up to date with the direction the wind blows
+/// towards, as a compass heading, and its speed.
+#[derive(Default)]
+pub struct WindHudSystem {
+    label: Option<Entity>,
+}
+
+impl<'s> System<'s> for WindHudSystem {
+    type SystemData = (UiFinder<'s>, WriteStorage<'s, UiText>, Read<'s, Wind>);
+
+    fn run(&mut self, (ui_finder, mut ui_texts, wind): Self::SystemData) {
+        // The UI prefab may not have finished loading yet.
+        if self.label.is_none() {
+            self.label = ui_finder.find(WIND_HUD_ID);
+        }
+        let label = match self.label.and_then(|label| ui_texts.get_mut(label)) {
+            Some(label) => label,
+            None => return,
+        };
+        let speed = wind.wind.magnitude();
+        label.text = if speed < std::f32::EPSILON {
+            "Wind: calm".to_string()
+        } else {
+            format!(
+                "Wind: {} {:.1}",
+                compass_label(wind.wind.y.atan2(wind.wind.x)),
+                speed
+            )
+        };
+    }
+}
+
+/// Returns the nearest of the eight compass headings for an angle in radians, measured
+/// counterclockwise from the positive x axis (east), with the positive y axis being north.
+pub fn compass_label(angle: f32) -> &'static str {
+    let heading = (PI / 2.0 - angle).rem_euclid(2.0 * PI);
+    let index = (heading / (PI / 4.0)).round() as usize % COMPASS_LABELS.len();
+    COMPASS_LABELS[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angles_map_to_compass_labels() {
+        let expected = [
+            (0.0, "E"),
+            (45.0, "NE"),
+            (90.0, "N"),
+            (135.0, "NW"),
+            (180.0, "W"),
+            (-135.0, "SW"),
+            (-90.0, "S"),
+            (-45.0, "SE"),
+        ];
+        for (degrees, label) in expected.iter() {
+            let angle = f32::to_radians(*degrees);
+            assert_eq!(compass_label(angle), *label);
+            // Anything within 22.5 degrees gets the same label.
+            assert_eq!(compass_label(angle + f32::to_radians(20.0)), *label);
+            assert_eq!(compass_label(angle - f32::to_radians(20.0)), *label);
+        }
+        assert_eq!(compass_label(f32::to_radians(-180.0)), "W");
+        assert_eq!(compass_label(f32::to_radians(360.0)), "E");
+    }
+}
@@ -0,0 +1,154 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time},
+    ecs::*,
+};
+
+use crate::resources::wind::{Wind, WindSpeedContributions};
+
+/// Combines the WindSpeedContributions of all systems into the actual wind speed. The combined
+/// speed is clamped to Wind::max_speed, and the wind speed changes by at most
+/// Wind::max_speed_change per second, so the topplegrass never gets a sudden kick.
+/// The direction of the wind is left alone, unless a new one is set in
+/// WindSpeedContributions::direction.
+/// If Wind::update_hz is set, the target speed is only recomputed on that schedule, and the
+/// system interpolates from the previous target to the latest one in between.
+#[derive(Default)]
+pub struct WindMagnitudeControllerSystem {
+    /// Seconds since the target speed was last recomputed.
+    since_update: f32,
+    previous_target: f32,
+    target: Option<f32>,
+    recomputes: usize,
+}
+
+impl<'s> System<'s> for WindMagnitudeControllerSystem {
+    type SystemData = (
+        Write<'s, Wind>,
+        Write<'s, WindSpeedContributions>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        // Start out from the wind speed that was configured, rather than from a standstill.
+        let base = world.fetch::<Wind>().wind.magnitude();
+        world.fetch_mut::<WindSpeedContributions>().base = base;
+    }
+
+    fn run(&mut self, (mut wind, mut contributions, time): Self::SystemData) {
+        self.since_update += time.delta_seconds();
+        let interval = if wind.update_hz > 0.0 {
+            1.0 / wind.update_hz
+        } else {
+            0.0
+        };
+        let due = match self.target {
+            None => true,
+            Some(_) => self.since_update >= interval,
+        };
+        if due {
+            let fresh = contributions.total().max(0.0).min(wind.max_speed);
+            self.previous_target = self.target.unwrap_or(fresh);
+            self.target = Some(fresh);
+            self.since_update = if interval > 0.0 {
+                (self.since_update - interval).max(0.0) % interval
+            } else {
+                0.0
+            };
+            self.recomputes += 1;
+        }
+        let latest = self.target.unwrap_or(0.0);
+        let target = if interval > 0.0 {
+            let progress = (self.since_update / interval).min(1.0);
+            self.previous_target + (latest - self.previous_target) * progress
+        } else {
+            latest
+        };
+        let current = wind.wind.magnitude();
+        let speed = limit_change(
+            current,
+            target,
+            wind.max_speed_change * time.delta_seconds(),
+        );
+        let direction = match contributions.direction.take() {
+            Some(direction) => direction,
+            None if current > std::f32::EPSILON => wind.wind / current,
+            None => Vector2::new(1.0, 0.0),
+        };
+        wind.wind = direction * speed;
+    }
+}
+
+/// Moves current towards target, by at most max_change.
+fn limit_change(current: f32, target: f32, max_change: f32) -> f32 {
+    current + (target - current).max(-max_change).min(max_change)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_jump_is_spread_over_multiple_frames() {
+        let mut world = World::new();
+        world.insert(Wind::new(1.0, 0.0));
+        let mut system = WindMagnitudeControllerSystem::default();
+        System::setup(&mut system, &mut world);
+        world.write_resource::<WindSpeedContributions>().base = 4.0;
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+
+        let mut speeds = Vec::new();
+        for _ in 0..4 {
+            system.run_now(&world);
+            speeds.push(world.read_resource::<Wind>().wind.x);
+        }
+
+        // max_speed_change defaults to 2.0 per second, so 1.0 per frame of half a second.
+        assert_eq!(speeds, vec![2.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn update_hz_limits_how_often_the_target_is_recomputed() {
+        let mut world = World::new();
+        let mut wind = Wind::new(1.0, 0.0);
+        wind.update_hz = 10.0;
+        world.insert(wind);
+        let mut system = WindMagnitudeControllerSystem::default();
+        System::setup(&mut system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(1.0 / 60.0);
+
+        for frame in 0..60 {
+            world.write_resource::<WindSpeedContributions>().base = 1.0 + (frame % 7) as f32 * 0.1;
+            system.run_now(&world);
+        }
+
+        assert!(
+            (9..=11).contains(&system.recomputes),
+            "recomputed {} times in one second",
+            system.recomputes
+        );
+    }
+
+    #[test]
+    fn calm_wind_picks_up_in_the_requested_direction() {
+        let mut world = World::new();
+        world.insert(Wind::new(0.0, 0.0));
+        let mut system = WindMagnitudeControllerSystem::default();
+        System::setup(&mut system, &mut world);
+        *world.write_resource::<WindSpeedContributions>() = WindSpeedContributions {
+            base: 1.0,
+            direction: Some(Vector2::new(0.0, 1.0)),
+        };
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+
+        system.run_now(&world);
+
+        let wind = world.read_resource::<Wind>().wind;
+        assert!(wind.x.abs() < 1e-6);
+        assert!((wind.y - 1.0).abs() < 1e-6);
+        assert_eq!(
+            world.read_resource::<WindSpeedContributions>().direction,
+            None
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use amethyst::{core::math::Vector2, ecs::*};
+
+use crate::{
+    components::{creatures::Movement, wind_migration::WindMigration},
+    resources::wind::Wind,
+};
+
+/// Steers the velocity of migrating entities towards the wind direction. The entity keeps its
+/// speed; only its heading is blended between its own wander direction and the wind.
+#[derive(Default)]
+pub struct WindMigrationSystem;
+
+impl<'s> System<'s> for WindMigrationSystem {
+    type SystemData = (
+        WriteStorage<'s, Movement>,
+        ReadStorage<'s, WindMigration>,
+        Read<'s, Wind>,
+    );
+
+    fn run(&mut self, (mut movements, migrations, wind): Self::SystemData) {
+        for (movement, migration) in (&mut movements, &migrations).join() {
+            let velocity = migrate(
+                Vector2::new(movement.velocity.x, movement.velocity.y),
+                wind.wind,
+                migration.strength,
+            );
+            movement.velocity.x = velocity.x;
+            movement.velocity.y = velocity.y;
+        }
+    }
+}
+
+/// Blends the heading of velocity with the wind direction by strength, keeping the speed.
+fn migrate(velocity: Vector2<f32>, wind: Vector2<f32>, strength: f32) -> Vector2<f32> {
+    let speed = velocity.magnitude();
+    if speed < std::f32::EPSILON || wind.magnitude() < std::f32::EPSILON {
+        return velocity;
+    }
+    let strength = strength.max(0.0).min(1.0);
+    let heading = velocity.normalize() * (1.0 - strength) + wind.normalize() * strength;
+    if heading.magnitude() < std::f32::EPSILON {
+        return velocity;
+    }
+    heading.normalize() * speed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strong_migrators_align_more_with_the_wind() {
+        let wind = Vector2::new(2.0, 0.0);
+        let velocity = Vector2::new(0.0, 1.0);
+        let weak = migrate(velocity, wind, 0.2);
+        let strong = migrate(velocity, wind, 0.8);
+        let alignment = |v: Vector2<f32>| v.normalize().dot(&wind.normalize());
+        assert!(alignment(strong) > alignment(weak));
+        assert!((strong.magnitude() - 1.0).abs() < 1e-6);
+    }
+}
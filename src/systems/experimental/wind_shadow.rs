@@ -0,0 +1,180 @@
+use amethyst::{
+    core::{math::Vector2, transform::Transform},
+    ecs::*,
+};
+
+use crate::{
+    components::{collider::Obstacle, creatures::TopplegrassTag, wind_shadow::WindShadow},
+    resources::{topplegrass::TopplegrassConfig, wind::Wind},
+};
+
+/// Shelters topplegrass in the lee of obstacles from the wind. For every topplegrass, checks
+/// whether it lies in the shadow cone of an obstacle, and stores how much of the wind still
+/// reaches it in its WindShadow component. Only the obstacle casting the deepest
+/// shadow counts, the shadows of several obstacles don't add up.
+#[derive(Default)]
+pub struct WindShadowSystem;
+
+impl<'s> System<'s> for WindShadowSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Obstacle>,
+        ReadStorage<'s, TopplegrassTag>,
+        WriteStorage<'s, WindShadow>,
+        Read<'s, Wind>,
+        Read<'s, TopplegrassConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, transforms, obstacles, topple_tags, mut wind_shadows, wind, config): Self::SystemData,
+    ) {
+        let obstacle_circles: Vec<(Vector2<f32>, f32)> = (&obstacles, &transforms)
+            .join()
+            .map(|(obstacle, transform)| (transform.translation().xy(), obstacle.collider.radius))
+            .collect();
+        for (entity, transform, _) in (&entities, &transforms, &topple_tags).join() {
+            let position = transform.translation().xy();
+            let local_wind = wind.at_height(transform.translation().z);
+            let factor = obstacle_circles
+                .iter()
+                .map(|(center, radius)| {
+                    shadow_factor(position, local_wind, *center, *radius, &config)
+                })
+                .fold(1.0, f32::min);
+            match wind_shadows.get_mut(entity) {
+                Some(wind_shadow) => wind_shadow.factor = factor,
+                None => {
+                    wind_shadows
+                        .insert(entity, WindShadow { factor })
+                        .expect("Unable to add wind shadow to entity");
+                }
+            }
+        }
+    }
+}
+
+/// Returns the fraction of the wind that reaches the position, given a single circular obstacle.
+/// The obstacle casts a cone shaped shadow downwind, as wide as the obstacle right behind it and
+/// widening by TopplegrassConfig::wind_shadow_spread for every unit further downwind. The shadow
+/// is shadow_length long: bigger obstacles and stronger winds cast longer shadows. Right behind
+/// the obstacle the wind is weakened by wind_shadow_strength, and it recovers linearly to full
+/// strength at the end of the shadow.
+pub fn shadow_factor(
+    position: Vector2<f32>,
+    wind: Vector2<f32>,
+    obstacle_center: Vector2<f32>,
+    obstacle_radius: f32,
+    config: &TopplegrassConfig,
+) -> f32 {
+    let length = shadow_length(obstacle_radius, wind.magnitude(), config);
+    if length <= 0.0 {
+        return 1.0;
+    }
+    let upwind = -wind.normalize();
+    let to_obstacle = obstacle_center - position;
+    let distance_upwind = to_obstacle.dot(&upwind);
+    if distance_upwind <= 0.0 || distance_upwind > length {
+        return 1.0;
+    }
+    let distance_sideways = (to_obstacle - upwind * distance_upwind).magnitude();
+    if distance_sideways > obstacle_radius + distance_upwind * config.wind_shadow_spread.max(0.0) {
+        return 1.0;
+    }
+    let depth = 1.0 - distance_upwind / length;
+    1.0 - config.wind_shadow_strength.max(0.0).min(1.0) * depth
+}
+
+/// Returns how far downwind an obstacle of the given radius shelters topplegrass from a wind of
+/// the given speed: TopplegrassConfig::wind_shadow_length obstacle radii at
+/// wind_shadow_reference_speed, growing and shrinking in proportion to the wind speed.
+pub fn shadow_length(obstacle_radius: f32, wind_speed: f32, config: &TopplegrassConfig) -> f32 {
+    if config.wind_shadow_reference_speed <= 0.0 || wind_speed < f32::EPSILON {
+        return 0.0;
+    }
+    (config.wind_shadow_length * obstacle_radius * wind_speed / config.wind_shadow_reference_speed)
+        .max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::collider::Circle;
+
+    #[test]
+    fn wind_is_weaker_directly_downwind_of_an_obstacle() {
+        let mut world = World::new();
+        let mut system = WindShadowSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(Wind::new(3.0, 0.0));
+        world.insert(TopplegrassConfig {
+            wind_shadow_strength: 0.8,
+            wind_shadow_length: 5.0,
+            wind_shadow_reference_speed: 3.0,
+            wind_shadow_spread: 0.2,
+            ..Default::default()
+        });
+        let mut obstacle_transform = Transform::default();
+        obstacle_transform.set_translation_xyz(0.0, 0.0, 0.5);
+        world
+            .create_entity()
+            .with(obstacle_transform)
+            .with(Obstacle {
+                collider: Circle { radius: 1.0 },
+            })
+            .build();
+        let topplegrass_at = |world: &mut World, x: f32, y: f32| {
+            let mut transform = Transform::default();
+            transform.set_translation_xyz(x, y, 0.5);
+            world
+                .create_entity()
+                .with(transform)
+                .with(TopplegrassTag)
+                .build()
+        };
+        let downwind = topplegrass_at(&mut world, 2.0, 0.0);
+        let upwind = topplegrass_at(&mut world, -2.0, 0.0);
+        let beside = topplegrass_at(&mut world, 2.0, 3.0);
+        let far_downwind = topplegrass_at(&mut world, 10.0, 0.0);
+
+        system.run_now(&world);
+
+        let wind_shadows = world.read_storage::<WindShadow>();
+        let factor = |entity| wind_shadows.get(entity).unwrap().factor;
+        // Two units behind an obstacle with a shadow five units long.
+        assert!((factor(downwind) - (1.0 - 0.8 * 0.6)).abs() < 1e-6);
+        assert_eq!(factor(upwind), 1.0);
+        assert_eq!(factor(beside), 1.0);
+        assert_eq!(factor(far_downwind), 1.0);
+    }
+
+    #[test]
+    fn shadows_widen_and_grow_with_obstacle_size_and_wind_speed() {
+        let config = TopplegrassConfig {
+            wind_shadow_strength: 1.0,
+            wind_shadow_length: 4.0,
+            wind_shadow_reference_speed: 2.0,
+            wind_shadow_spread: 0.5,
+            ..Default::default()
+        };
+        let wind = Vector2::new(2.0, 0.0);
+        let obstacle = Vector2::zeros();
+        let factor = |position: Vector2<f32>, wind: Vector2<f32>, radius: f32| {
+            shadow_factor(position, wind, obstacle, radius, &config)
+        };
+        // The sampled wind downwind of the obstacle is weaker than upwind of it.
+        let downwind = wind * factor(Vector2::new(2.0, 0.0), wind, 1.0);
+        let upwind = wind * factor(Vector2::new(-2.0, 0.0), wind, 1.0);
+        assert!(downwind.magnitude() < upwind.magnitude());
+        // Two units downwind, the cone is 1.0 + 2.0 * 0.5 = 2.0 units wide on either side.
+        assert!(factor(Vector2::new(2.0, 1.9), wind, 1.0) < 1.0);
+        assert_eq!(factor(Vector2::new(2.0, 2.1), wind, 1.0), 1.0);
+        // At the reference speed, the shadow is four radii long.
+        assert!(factor(Vector2::new(3.9, 0.0), wind, 1.0) < 1.0);
+        assert_eq!(factor(Vector2::new(4.1, 0.0), wind, 1.0), 1.0);
+        assert!(factor(Vector2::new(7.9, 0.0), wind, 2.0) < 1.0);
+        assert!(factor(Vector2::new(7.9, 0.0), wind * 2.0, 1.0) < 1.0);
+        assert_eq!(factor(Vector2::new(2.1, 0.0), wind * 0.5, 1.0), 1.0);
+    }
+}
@@ -0,0 +1,43 @@
+use amethyst::ecs::*;
+
+use crate::resources::wind::Wind;
+
+/// Records the current wind every frame, so Wind::smoothed() averages over the most recent frames.
+#[derive(Default)]
+pub struct WindSmoothingSystem;
+
+impl<'s> System<'s> for WindSmoothingSystem {
+    type SystemData = Write<'s, Wind>;
+
+    fn run(&mut self, mut wind: Self::SystemData) {
+        wind.record_sample();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::core::math::Vector2;
+
+    #[test]
+    fn step_change_in_wind_is_smoothed_over_several_frames() {
+        let mut world = World::new();
+        let mut system = WindSmoothingSystem;
+        System::setup(&mut system, &mut world);
+        let mut wind = Wind::new(0.0, 0.0);
+        wind.smoothing_samples = 4;
+        world.insert(wind);
+        for _ in 0..4 {
+            system.run_now(&world);
+        }
+
+        world.write_resource::<Wind>().wind = Vector2::new(4.0, 0.0);
+        let mut smoothed = Vec::new();
+        for _ in 0..4 {
+            system.run_now(&world);
+            smoothed.push(world.read_resource::<Wind>().smoothed().x);
+        }
+
+        assert_eq!(smoothed, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+}
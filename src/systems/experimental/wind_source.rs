@@ -0,0 +1,267 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time},
+    ecs::*,
+};
+
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use crate::resources::{wind::WindSpeedContributions, wind_source::WindSourceConfig};
+
+/// A source of wind samples from outside the simulation.
+pub trait WindSource: Send {
+    /// Returns the newest wind sample, if one arrived since the last poll.
+    fn poll(&mut self) -> Option<Vector2<f32>>;
+}
+
+/// Reads the wind from the last line of a file whenever the file changes.
+pub struct FileWindSource {
+    path: PathBuf,
+    last_contents: Option<String>,
+}
+
+impl FileWindSource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> FileWindSource {
+        FileWindSource {
+            path: path.into(),
+            last_contents: None,
+        }
+    }
+}
+
+impl WindSource for FileWindSource {
+    fn poll(&mut self) -> Option<Vector2<f32>> {
+        // The file may not have been written yet, so a failed read just means no new sample.
+        let contents = fs::read_to_string(&self.path).ok()?;
+        if self.last_contents.as_ref() == Some(&contents) {
+            return None;
+        }
+        let sample = parse_wind_sample(&contents);
+        if sample.is_none() {
+            warn!("Could not read a wind sample from {:?}", self.path);
+        }
+        self.last_contents = Some(contents);
+        sample
+    }
+}
+
+/// Hands out wind samples pushed into it from elsewhere in the program, oldest first. Clones
+/// share the same queue, so one can be given to the WindSourceSystem while another is fed.
+#[derive(Clone, Default)]
+pub struct InMemoryWindSource {
+    samples: Arc<Mutex<VecDeque<Vector2<f32>>>>,
+}
+
+impl InMemoryWindSource {
+    pub fn push(&self, sample: Vector2<f32>) {
+        self.samples.lock().unwrap().push_back(sample);
+    }
+}
+
+impl WindSource for InMemoryWindSource {
+    fn poll(&mut self) -> Option<Vector2<f32>> {
+        self.samples.lock().unwrap().pop_front()
+    }
+}
+
+/// Receives wind samples as UDP datagrams, each holding a single sample.
+#[cfg(feature = "wind-source-udp")]
+pub struct UdpWindSource {
+    socket: std::net::UdpSocket,
+}
+
+#[cfg(feature = "wind-source-udp")]
+impl UdpWindSource {
+    pub fn bind(address: &str) -> std::io::Result<UdpWindSource> {
+        let socket = std::net::UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(UdpWindSource { socket })
+    }
+}
+
+#[cfg(feature = "wind-source-udp")]
+impl WindSource for UdpWindSource {
+    fn poll(&mut self) -> Option<Vector2<f32>> {
+        let mut buffer = [0; 256];
+        let mut latest = None;
+        // Drain every datagram that arrived since the last poll, keeping only the newest sample.
+        while let Ok(length) = self.socket.recv(&mut buffer) {
+            if let Some(sample) = std::str::from_utf8(&buffer[..length])
+                .ok()
+                .and_then(parse_wind_sample)
+            {
+                latest = Some(sample);
+            }
+        }
+        latest
+    }
+}
+
+/// Parses the last non-empty line of the text as the x and y of the wind, separated by a comma
+/// and/or whitespace.
+pub fn parse_wind_sample(text: &str) -> Option<Vector2<f32>> {
+    let line = text.lines().rev().find(|line| !line.trim().is_empty())?;
+    let values = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse::<f32>().ok())
+        .collect::<Option<Vec<f32>>>()?;
+    match values.as_slice() {
+        [x, y] => Some(Vector2::new(*x, *y)),
+        _ => None,
+    }
+}
+
+/// Drives the wind from a WindSource, polled every WindSourceConfig::poll_interval seconds.
+/// A new sample sets the direction of the wind and its base speed in the WindSpeedContributions,
+/// so the WindMagnitudeControllerSystem eases the wind speed towards it rather than jumping.
+/// Unless a source is given, it is created in setup from the WindSourceConfig.
+#[derive(Default)]
+pub struct WindSourceSystem {
+    source: Option<Box<dyn WindSource>>,
+    since_poll: f32,
+}
+
+impl WindSourceSystem {
+    pub fn new(source: Box<dyn WindSource>) -> WindSourceSystem {
+        WindSourceSystem {
+            source: Some(source),
+            since_poll: 0.0,
+        }
+    }
+}
+
+impl<'s> System<'s> for WindSourceSystem {
+    type SystemData = (
+        Write<'s, WindSpeedContributions>,
+        Read<'s, Time>,
+        Read<'s, WindSourceConfig>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        if self.source.is_none() {
+            self.source = source_from_config(&world.fetch::<WindSourceConfig>());
+        }
+    }
+
+    fn run(&mut self, (mut contributions, time, config): Self::SystemData) {
+        let source = match self.source.as_mut() {
+            Some(source) => source,
+            None => return,
+        };
+        self.since_poll += time.delta_seconds();
+        if self.since_poll < config.poll_interval {
+            return;
+        }
+        self.since_poll = 0.0;
+        if let Some(sample) = source.poll() {
+            let speed = sample.magnitude();
+            if speed > std::f32::EPSILON {
+                contributions.direction = Some(sample / speed);
+            }
+            contributions.base = speed;
+        }
+    }
+}
+
+fn source_from_config(config: &WindSourceConfig) -> Option<Box<dyn WindSource>> {
+    if let Some(file) = &config.file {
+        return Some(Box::new(FileWindSource::new(file.clone())));
+    }
+    config.udp_address.as_deref().and_then(udp_source)
+}
+
+#[cfg(feature = "wind-source-udp")]
+fn udp_source(address: &str) -> Option<Box<dyn WindSource>> {
+    match UdpWindSource::bind(address) {
+        Ok(source) => Some(Box::new(source)),
+        Err(error) => {
+            error!(
+                "Failed to listen for wind samples on {}: {:?}",
+                address, error
+            );
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "wind-source-udp"))]
+fn udp_source(address: &str) -> Option<Box<dyn WindSource>> {
+    warn!(
+        "Ignoring wind source address {}, built without the wind-source-udp feature",
+        address
+    );
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{resources::wind::Wind, systems::wind_magnitude::WindMagnitudeControllerSystem};
+
+    #[test]
+    fn file_source_reads_the_updated_value() {
+        let path = std::env::temp_dir().join(format!(
+            "evoli_{}_file_source_reads_the_updated_value.txt",
+            std::process::id()
+        ));
+        fs::write(&path, "1.0, 2.0\n").unwrap();
+        let mut source = FileWindSource::new(path.clone());
+
+        assert_eq!(source.poll(), Some(Vector2::new(1.0, 2.0)));
+        assert_eq!(source.poll(), None);
+
+        fs::write(&path, "1.0, 2.0\n-3.5 0.25\n").unwrap();
+        let updated = source.poll();
+        let _ = fs::remove_file(&path);
+        assert_eq!(updated, Some(Vector2::new(-3.5, 0.25)));
+    }
+
+    #[test]
+    fn new_sample_sets_the_wind_direction_and_base_speed() {
+        let mut world = World::new();
+        world.insert(WindSourceConfig {
+            poll_interval: 0.0,
+            ..WindSourceConfig::default()
+        });
+        let source = InMemoryWindSource::default();
+        let mut system = WindSourceSystem::new(Box::new(source.clone()));
+        System::setup(&mut system, &mut world);
+
+        source.push(Vector2::new(0.0, 4.0));
+        system.run_now(&world);
+
+        let contributions = world.read_resource::<WindSpeedContributions>();
+        assert_eq!(contributions.direction, Some(Vector2::new(0.0, 1.0)));
+        assert!((contributions.base - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sample_from_calm_wind_blows_in_its_direction() {
+        let mut world = World::new();
+        world.insert(Wind::new(0.0, 0.0));
+        world.insert(WindSourceConfig {
+            poll_interval: 0.0,
+            ..WindSourceConfig::default()
+        });
+        let source = InMemoryWindSource::default();
+        let mut wind_source = WindSourceSystem::new(Box::new(source.clone()));
+        let mut controller = WindMagnitudeControllerSystem::default();
+        System::setup(&mut wind_source, &mut world);
+        System::setup(&mut controller, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+
+        source.push(Vector2::new(0.0, 4.0));
+        wind_source.run_now(&world);
+        controller.run_now(&world);
+
+        let wind = world.read_resource::<Wind>().wind;
+        assert!(wind.x.abs() < 1e-6);
+        assert!(wind.y > 0.0);
+    }
+}
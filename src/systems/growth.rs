@@ -0,0 +1,52 @@
+use amethyst::{core::Time, ecs::*};
+
+use crate::components::creatures::{Growth, Size};
+
+/// Makes young creatures grow up. Increases the Size of every entity with a Growth component
+/// over time, until the maximum size is reached.
+pub struct GrowthSystem;
+impl<'s> System<'s> for GrowthSystem {
+    type SystemData = (
+        ReadStorage<'s, Growth>,
+        WriteStorage<'s, Size>,
+        Read<'s, Time>,
+    );
+
+    fn run(&mut self, (growths, mut sizes, time): Self::SystemData) {
+        let delta_time = time.delta_seconds();
+        for (growth, size) in (&growths, &mut sizes).join() {
+            if size.value < growth.max_size {
+                size.value = (size.value + growth.rate * delta_time).min(growth.max_size);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_grows_until_max_size() {
+        let mut world = World::new();
+        let mut system = GrowthSystem;
+        System::setup(&mut system, &mut world);
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+        let entity = world
+            .create_entity()
+            .with(Size { value: 1.0 })
+            .with(Growth {
+                rate: 0.25,
+                max_size: 1.6,
+            })
+            .build();
+
+        system.run_now(&world);
+        assert!((world.read_storage::<Size>().get(entity).unwrap().value - 1.25).abs() < 1e-6);
+
+        for _ in 0..10 {
+            system.run_now(&world);
+        }
+        assert!((world.read_storage::<Size>().get(entity).unwrap().value - 1.6).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,112 @@
+use amethyst::{
+    core::math::Vector3,
+    ecs::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::components::creatures::Movement;
+
+/// Requests a sudden change in the velocity of the target entity. Systems that want to push
+/// entities around, such as wind bursts or explosions, should send an ImpulseEvent instead of
+/// changing the Movement directly.
+#[derive(Debug, Clone)]
+pub struct ImpulseEvent {
+    pub target: Entity,
+    pub impulse: Vector3<f32>,
+}
+
+/// Adds the impulse of every ImpulseEvent to the velocity of its target. The horizontal part of
+/// the resulting velocity is clamped to the target's max movement speed.
+#[derive(Default)]
+pub struct ImpulseSystem {
+    impulse_reader_id: Option<ReaderId<ImpulseEvent>>,
+}
+
+impl<'s> System<'s> for ImpulseSystem {
+    type SystemData = (
+        Read<'s, EventChannel<ImpulseEvent>>,
+        WriteStorage<'s, Movement>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.impulse_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<ImpulseEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (impulse_events, mut movements): Self::SystemData) {
+        for event in impulse_events.read(self.impulse_reader_id.as_mut().unwrap()) {
+            if let Some(movement) = movements.get_mut(event.target) {
+                movement.velocity = apply_impulse(
+                    movement.velocity,
+                    event.impulse,
+                    movement.max_movement_speed,
+                );
+            }
+        }
+    }
+}
+
+/// Returns the velocity after adding the impulse, with its horizontal part clamped to max_speed.
+fn apply_impulse(velocity: Vector3<f32>, impulse: Vector3<f32>, max_speed: f32) -> Vector3<f32> {
+    let mut new_velocity = velocity + impulse;
+    let horizontal_speed = new_velocity.xy().magnitude();
+    if horizontal_speed > max_speed {
+        let factor = max_speed / horizontal_speed;
+        new_velocity.x *= factor;
+        new_velocity.y *= factor;
+    }
+    new_velocity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impulse_changes_the_velocity_of_its_target() {
+        let mut world = World::new();
+        let mut system = ImpulseSystem::default();
+        System::setup(&mut system, &mut world);
+        let target = world
+            .create_entity()
+            .with(Movement {
+                velocity: Vector3::new(1.0, 0.0, 0.0),
+                max_movement_speed: 3.0,
+            })
+            .build();
+        let bystander = world
+            .create_entity()
+            .with(Movement {
+                velocity: Vector3::zeros(),
+                max_movement_speed: 3.0,
+            })
+            .build();
+        world
+            .write_resource::<EventChannel<ImpulseEvent>>()
+            .single_write(ImpulseEvent {
+                target,
+                impulse: Vector3::new(0.5, 1.0, 2.0),
+            });
+
+        system.run_now(&world);
+
+        let movements = world.read_storage::<Movement>();
+        assert_eq!(
+            movements.get(target).unwrap().velocity,
+            Vector3::new(1.5, 1.0, 2.0)
+        );
+        assert_eq!(movements.get(bystander).unwrap().velocity, Vector3::zeros());
+    }
+
+    #[test]
+    fn horizontal_velocity_is_clamped_to_max_speed() {
+        let velocity = apply_impulse(Vector3::zeros(), Vector3::new(6.0, 8.0, 4.0), 5.0);
+        assert!((velocity.x - 3.0).abs() < 1e-5);
+        assert!((velocity.y - 4.0).abs() < 1e-5);
+        assert_eq!(velocity.z, 4.0);
+    }
+}
@@ -4,12 +4,23 @@ pub mod collision;
 pub mod combat;
 pub mod death;
 pub mod debug;
+pub mod debug_input;
 pub mod digestion;
+pub mod growth;
 pub mod health;
+pub mod impulse;
 pub mod main_game_ui;
 pub mod movement;
+pub mod picking;
+pub mod replay;
+pub mod reset;
+pub mod size;
 pub mod spawner;
+pub mod species_color;
+pub mod stats_export;
 pub mod swarm_behavior;
+pub mod timeline;
+pub mod world_init;
 
 mod experimental;
 pub use experimental::*;
@@ -1,6 +1,10 @@
-use amethyst::{core::transform::Transform, core::Time, ecs::*};
+use amethyst::{core::math::Vector3, core::transform::Transform, core::Time, ecs::*};
+
+use std::collections::HashMap;
 
 use crate::components::creatures::{CreatureTag, Movement};
+use crate::components::pinned::Pinned;
+use crate::resources::physics::PhysicsConfig;
 
 pub struct MovementSystem;
 impl<'s> System<'s> for MovementSystem {
@@ -8,12 +12,16 @@ impl<'s> System<'s> for MovementSystem {
         WriteStorage<'s, Movement>,
         WriteStorage<'s, Transform>,
         ReadStorage<'s, CreatureTag>,
+        ReadStorage<'s, Pinned>,
         Read<'s, Time>,
     );
 
-    fn run(&mut self, (mut movements, mut transforms, creature_tags, time): Self::SystemData) {
+    fn run(
+        &mut self,
+        (mut movements, mut transforms, creature_tags, pinned, time): Self::SystemData,
+    ) {
         let delta_time = time.delta_seconds();
-        for (movement, transform) in (&mut movements, &mut transforms).join() {
+        for (movement, transform, _) in (&mut movements, &mut transforms, !&pinned).join() {
             let magnitude = movement.velocity.magnitude();
             if magnitude > movement.max_movement_speed {
                 movement.velocity = movement.velocity * (movement.max_movement_speed / magnitude);
@@ -28,3 +36,214 @@ impl<'s> System<'s> for MovementSystem {
         }
     }
 }
+
+/// Guards the physics pipeline against NaN and infinite values, for example from normalizing a
+/// zero vector, which would otherwise spread and make entities vanish. Every velocity and
+/// translation that isn't finite is logged along with the entity and its other values. If
+/// reset_invalid is set, which it is by default, a bad velocity is reset to zero and a bad
+/// translation to the last valid one seen, or the origin. Only active in debug builds.
+pub struct SanityCheckSystem {
+    pub reset_invalid: bool,
+    /// Number of non-finite velocities and translations found so far.
+    pub detected: usize,
+    last_valid_translations: HashMap<Entity, Vector3<f32>>,
+}
+
+impl Default for SanityCheckSystem {
+    fn default() -> Self {
+        SanityCheckSystem {
+            reset_invalid: true,
+            detected: 0,
+            last_valid_translations: HashMap::new(),
+        }
+    }
+}
+
+impl<'s> System<'s> for SanityCheckSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Movement>,
+        WriteStorage<'s, Transform>,
+    );
+
+    fn run(&mut self, (entities, mut movements, mut transforms): Self::SystemData) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        for (entity, movement, transform) in
+            (&entities, &mut movements, (&transforms).maybe()).join()
+        {
+            if !is_finite(&movement.velocity) {
+                self.detected += 1;
+                warn!(
+                    "Entity {:?} has a non-finite velocity {:?} (max speed {}, translation {:?}){}",
+                    entity.id(),
+                    movement.velocity,
+                    movement.max_movement_speed,
+                    transform.map(|transform| transform.translation()),
+                    if self.reset_invalid {
+                        ", resetting it to zero"
+                    } else {
+                        ""
+                    }
+                );
+                if self.reset_invalid {
+                    movement.velocity = Vector3::zeros();
+                }
+            }
+        }
+        for (entity, transform, movement) in
+            (&entities, &mut transforms, (&movements).maybe()).join()
+        {
+            let translation = *transform.translation();
+            if is_finite(&translation) {
+                self.last_valid_translations.insert(entity, translation);
+                continue;
+            }
+            self.detected += 1;
+            let last_valid = self.last_valid_translations.get(&entity).cloned();
+            warn!(
+                "Entity {:?} has a non-finite translation {:?} (velocity {:?}, last valid translation {:?}){}",
+                entity.id(),
+                translation,
+                movement.map(|movement| movement.velocity),
+                last_valid,
+                if self.reset_invalid {
+                    ", moving it back"
+                } else {
+                    ""
+                }
+            );
+            if self.reset_invalid {
+                *transform.translation_mut() = last_valid.unwrap_or_else(Vector3::zeros);
+            }
+        }
+        self.last_valid_translations
+            .retain(|entity, _| entities.is_alive(*entity));
+    }
+}
+
+/// Clamps the velocity of every entity to PhysicsConfig::global_speed_limit, independent of its
+/// own max_movement_speed.
+pub struct GlobalSpeedLimitSystem;
+impl<'s> System<'s> for GlobalSpeedLimitSystem {
+    type SystemData = (WriteStorage<'s, Movement>, Read<'s, PhysicsConfig>);
+
+    fn run(&mut self, (mut movements, config): Self::SystemData) {
+        let limit = config.global_speed_limit;
+        for movement in (&mut movements).join() {
+            let magnitude = movement.velocity.magnitude();
+            if magnitude > limit {
+                movement.velocity *= limit / magnitude;
+            }
+        }
+    }
+}
+
+fn is_finite(vector: &Vector3<f32>) -> bool {
+    vector.iter().all(|component| component.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The SanityCheckSystem does nothing in release builds.
+    #[test]
+    #[cfg(debug_assertions)]
+    fn nan_velocity_is_reset_to_zero() {
+        let mut world = World::new();
+        let mut system = SanityCheckSystem::default();
+        System::setup(&mut system, &mut world);
+        let entity = world
+            .create_entity()
+            .with(Movement {
+                velocity: Vector3::new(std::f32::NAN, 1.0, 0.0),
+                max_movement_speed: 1.0,
+            })
+            .build();
+
+        system.run_now(&world);
+
+        let movements = world.read_storage::<Movement>();
+        assert_eq!(movements.get(entity).unwrap().velocity, Vector3::zeros());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn nan_translation_is_detected_and_moved_back() {
+        let mut world = World::new();
+        let mut system = SanityCheckSystem::default();
+        System::setup(&mut system, &mut world);
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(1.0, 2.0, 0.5);
+        let entity = world
+            .create_entity()
+            .with(transform)
+            .with(Movement {
+                velocity: Vector3::new(1.0, 0.0, 0.0),
+                max_movement_speed: 1.0,
+            })
+            .build();
+
+        system.run_now(&world);
+        assert_eq!(system.detected, 0);
+
+        world
+            .write_storage::<Transform>()
+            .get_mut(entity)
+            .unwrap()
+            .set_translation_x(std::f32::NAN);
+        world
+            .write_storage::<Movement>()
+            .get_mut(entity)
+            .unwrap()
+            .velocity
+            .y = std::f32::INFINITY;
+        system.run_now(&world);
+
+        assert_eq!(system.detected, 2);
+        let transforms = world.read_storage::<Transform>();
+        assert_eq!(
+            *transforms.get(entity).unwrap().translation(),
+            Vector3::new(1.0, 2.0, 0.5)
+        );
+        let movements = world.read_storage::<Movement>();
+        assert_eq!(movements.get(entity).unwrap().velocity, Vector3::zeros());
+    }
+
+    #[test]
+    fn global_speed_limit_overrides_max_movement_speed() {
+        let mut world = World::new();
+        let mut system = GlobalSpeedLimitSystem;
+        System::setup(&mut system, &mut world);
+        world.insert(PhysicsConfig {
+            global_speed_limit: 10.0,
+            ..Default::default()
+        });
+        let fast = world
+            .create_entity()
+            .with(Movement {
+                velocity: Vector3::new(30.0, 40.0, 0.0),
+                max_movement_speed: 1000.0,
+            })
+            .build();
+        let slow = world
+            .create_entity()
+            .with(Movement {
+                velocity: Vector3::new(3.0, 4.0, 0.0),
+                max_movement_speed: 1000.0,
+            })
+            .build();
+
+        system.run_now(&world);
+
+        let movements = world.read_storage::<Movement>();
+        let velocity = movements.get(fast).unwrap().velocity;
+        assert!((velocity - Vector3::new(6.0, 8.0, 0.0)).magnitude() < 1e-4);
+        assert_eq!(
+            movements.get(slow).unwrap().velocity,
+            Vector3::new(3.0, 4.0, 0.0)
+        );
+    }
+}
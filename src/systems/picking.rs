@@ -0,0 +1,203 @@
+use amethyst::{
+    core::{
+        math::{Point2, Vector2, Vector3},
+        Transform,
+    },
+    ecs::*,
+    input::{InputHandler, StringBindings},
+    renderer::camera::Camera,
+    shrev::{EventChannel, ReaderId},
+    window::ScreenDimensions,
+};
+
+use crate::{
+    components::{
+        collider::Circle,
+        creatures::{FallingTag, Movement, TopplegrassTag},
+        pinned::Pinned,
+    },
+    resources::debug::{DebugConfig, PickedEntity},
+    systems::debug_input::DebugCommand,
+};
+
+/// Topplegrass without a collider, or with a smaller one, can be picked within this distance.
+const PICK_RADIUS: f32 = 0.5;
+
+/// While debug mode is on, picks the topplegrass under the cursor on a PickEntity command and
+/// logs its velocity, scale and whether it is falling. The picked entity is kept in the
+/// PickedEntity resource, so other debug tools can act on it. A TogglePinned command pins the
+/// picked entity in place, or sets it loose again.
+#[derive(Default)]
+pub struct PickingSystem {
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+}
+
+impl<'s> System<'s> for PickingSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Camera>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Movement>,
+        ReadStorage<'s, Circle>,
+        ReadStorage<'s, TopplegrassTag>,
+        ReadStorage<'s, FallingTag>,
+        Read<'s, InputHandler<StringBindings>>,
+        ReadExpect<'s, ScreenDimensions>,
+        Read<'s, DebugConfig>,
+        Read<'s, EventChannel<DebugCommand>>,
+        Write<'s, PickedEntity>,
+        WriteStorage<'s, Pinned>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            cameras,
+            transforms,
+            movements,
+            circles,
+            topple_tags,
+            falling_tags,
+            input_handler,
+            screen_dimensions,
+            debug_config,
+            debug_commands,
+            mut picked_entity,
+            mut pinned,
+        ): Self::SystemData,
+    ) {
+        let mut pick_requested = false;
+        let mut pin_toggled = false;
+        for command in debug_commands.read(self.command_reader_id.as_mut().unwrap()) {
+            match command {
+                DebugCommand::PickEntity => pick_requested = true,
+                DebugCommand::TogglePinned => pin_toggled = !pin_toggled,
+                _ => (),
+            }
+        }
+        if !debug_config.visible {
+            return;
+        }
+        if pin_toggled {
+            if let Some(entity) = picked_entity.0.filter(|entity| entities.is_alive(*entity)) {
+                if pinned.remove(entity).is_none() {
+                    pinned.insert(entity, Pinned).expect("Unable to pin entity");
+                }
+                info!(
+                    "Topplegrass {:?} pinned: {}",
+                    entity.id(),
+                    pinned.contains(entity)
+                );
+            }
+        }
+        if !pick_requested {
+            return;
+        }
+        let (mouse_x, mouse_y) = match input_handler.mouse_position() {
+            Some(position) => position,
+            None => return,
+        };
+        let (camera, camera_transform) = match (&cameras, &transforms).join().next() {
+            Some(camera) => camera,
+            None => return,
+        };
+        let ray = camera.projection().screen_ray(
+            Point2::new(mouse_x, mouse_y),
+            Vector2::new(screen_dimensions.width(), screen_dimensions.height()),
+            camera_transform,
+        );
+
+        let closest = (&entities, &transforms, circles.maybe(), &topple_tags)
+            .join()
+            .filter_map(|(entity, transform, circle, _)| {
+                let center = transform.global_matrix().column(3).xyz();
+                let radius = circle.map_or(PICK_RADIUS, |circle| circle.radius.max(PICK_RADIUS));
+                ray_sphere_intersection(ray.origin.coords, ray.direction, center, radius)
+                    .map(|distance| (entity, distance))
+            })
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap());
+        picked_entity.0 = closest.map(|(entity, _)| entity);
+
+        if let Some(entity) = picked_entity.0 {
+            let transform = transforms.get(entity).unwrap();
+            info!(
+                "Picked topplegrass {:?}: velocity {:?}, scale {:?}, falling: {}",
+                entity.id(),
+                movements.get(entity).map(|movement| movement.velocity),
+                transform.scale(),
+                falling_tags.contains(entity)
+            );
+        }
+    }
+}
+
+/// Returns the distance along a ray to where it first hits a sphere, or None if it misses.
+/// The direction of the ray must be normalized. If the ray starts inside the sphere, the distance
+/// is zero.
+pub fn ray_sphere_intersection(
+    origin: Vector3<f32>,
+    direction: Vector3<f32>,
+    center: Vector3<f32>,
+    radius: f32,
+) -> Option<f32> {
+    let to_center = center - origin;
+    let closest_approach = to_center.dot(&direction);
+    let squared_distance = to_center.magnitude_squared() - closest_approach * closest_approach;
+    let squared_radius = radius * radius;
+    if squared_distance > squared_radius {
+        return None;
+    }
+    let half_chord = (squared_radius - squared_distance).sqrt();
+    let exit = closest_approach + half_chord;
+    if exit < 0.0 {
+        // The sphere is behind the ray.
+        return None;
+    }
+    Some((closest_approach - half_chord).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_hits_and_misses_a_sphere() {
+        let center = Vector3::new(0.0, 0.0, -10.0);
+        let down = Vector3::new(0.0, 0.0, -1.0);
+
+        let hit = ray_sphere_intersection(Vector3::zeros(), down, center, 2.0);
+        assert!((hit.unwrap() - 8.0).abs() < 1e-5);
+
+        // Passing just beside the sphere.
+        let beside = Vector3::new(2.1, 0.0, 0.0);
+        assert_eq!(ray_sphere_intersection(beside, down, center, 2.0), None);
+
+        // Pointing away from the sphere.
+        assert_eq!(
+            ray_sphere_intersection(Vector3::zeros(), -down, center, 2.0),
+            None
+        );
+
+        // Starting inside the sphere.
+        let inside = Vector3::new(0.0, 1.0, -10.0);
+        assert_eq!(
+            ray_sphere_intersection(inside, down, center, 2.0),
+            Some(0.0)
+        );
+
+        // At an angle, straight through the center of a small sphere.
+        let diagonal = Vector3::new(1.0, 0.0, -1.0).normalize();
+        let hit = ray_sphere_intersection(Vector3::new(-10.0, 0.0, 0.0), diagonal, center, 0.1);
+        assert!((hit.unwrap() - (200.0f32.sqrt() - 0.1)).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,231 @@
+use amethyst::{
+    core::Time,
+    ecs::*,
+    prelude::Config,
+    shrev::{EventChannel, ReaderId},
+};
+use std::{
+    fs::File,
+    io::{self, Seek, SeekFrom, Write as IoWrite},
+};
+
+use crate::{
+    resources::replay::{CommandRecording, RecordedCommand, ReplayConfig, ReplayMode},
+    systems::debug_input::DebugCommand,
+};
+
+/// Opens a CommandRecording with no commands; commands are inserted before the trailer.
+const RECORDING_HEADER: &str = "(\n    commands: [\n";
+const RECORDING_TRAILER: &str = "    ],\n)\n";
+
+/// Records every DebugCommand together with the simulated time at which it was issued, and writes
+/// the recording to the configured file. Only active when the ReplayMode is Record.
+/// New commands are written over the end of the file, so the file holds a loadable
+/// CommandRecording after every frame without being rewritten.
+#[derive(Default)]
+pub struct CommandRecorderSystem {
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+    elapsed: f64,
+    file: Option<File>,
+    failed: bool,
+}
+
+impl<'s> System<'s> for CommandRecorderSystem {
+    type SystemData = (
+        Read<'s, EventChannel<DebugCommand>>,
+        Read<'s, Time>,
+        Read<'s, ReplayConfig>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (debug_commands, time, config): Self::SystemData) {
+        self.elapsed += f64::from(time.delta_seconds());
+        let commands = debug_commands.read(self.command_reader_id.as_mut().unwrap());
+        if config.mode != ReplayMode::Record || self.failed {
+            return;
+        }
+        let recorded: Vec<RecordedCommand> = commands
+            .map(|command| RecordedCommand {
+                time: self.elapsed,
+                command: *command,
+            })
+            .collect();
+        if recorded.is_empty() {
+            return;
+        }
+        if let Err(error) = self.append(&config.recording_path, &recorded) {
+            error!(
+                "Failed to write command recording to {}. Stopping the recording. Error: {:?}",
+                config.recording_path, error
+            );
+            self.failed = true;
+        }
+    }
+}
+
+impl CommandRecorderSystem {
+    fn append(&mut self, path: &str, commands: &[RecordedCommand]) -> io::Result<()> {
+        if self.file.is_none() {
+            let mut file = File::create(path)?;
+            file.write_all(RECORDING_HEADER.as_bytes())?;
+            file.write_all(RECORDING_TRAILER.as_bytes())?;
+            self.file = Some(file);
+        }
+        let file = self.file.as_mut().unwrap();
+        file.seek(SeekFrom::End(-(RECORDING_TRAILER.len() as i64)))?;
+        for command in commands {
+            let entry = ron::ser::to_string(command)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            writeln!(file, "        {},", entry)?;
+        }
+        file.write_all(RECORDING_TRAILER.as_bytes())?;
+        file.flush()
+    }
+}
+
+/// Plays back previously recorded DebugCommands at the simulated times at which they were
+/// recorded. Only active when the ReplayMode is Playback.
+#[derive(Default)]
+pub struct CommandPlaybackSystem {
+    recording: CommandRecording,
+    next_command: usize,
+    elapsed: f64,
+}
+
+impl CommandPlaybackSystem {
+    pub fn new(recording: CommandRecording) -> CommandPlaybackSystem {
+        CommandPlaybackSystem {
+            recording,
+            next_command: 0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl<'s> System<'s> for CommandPlaybackSystem {
+    type SystemData = (Write<'s, EventChannel<DebugCommand>>, Read<'s, Time>);
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        let config = world
+            .entry::<ReplayConfig>()
+            .or_insert_with(ReplayConfig::default);
+        if config.mode == ReplayMode::Playback {
+            self.recording = CommandRecording::load(&config.recording_path).unwrap_or_else(|error| {
+                error!("Failed to load command recording from {}. Nothing will be played back. Error: {:?}", config.recording_path, error);
+                CommandRecording::default()
+            });
+        }
+    }
+
+    fn run(&mut self, (mut debug_commands, time): Self::SystemData) {
+        self.elapsed += f64::from(time.delta_seconds());
+        while let Some(recorded) = self.recording.commands.get(self.next_command) {
+            if recorded.time > self.elapsed {
+                break;
+            }
+            debug_commands.single_write(recorded.command);
+            self.next_command += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn commands_recorded_over_several_frames_load_back() {
+        let path = std::env::temp_dir().join(format!(
+            "evoli_{}_commands_recorded_over_several_frames_load_back.ron",
+            std::process::id()
+        ));
+        let mut world = World::new();
+        let mut system = CommandRecorderSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(ReplayConfig {
+            mode: ReplayMode::Record,
+            recording_path: path.to_str().unwrap().to_string(),
+        });
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+
+        for commands in &[
+            vec![DebugCommand::TogglePause],
+            vec![],
+            vec![DebugCommand::SpeedUp, DebugCommand::SlowDown],
+        ] {
+            world
+                .write_resource::<EventChannel<DebugCommand>>()
+                .iter_write(commands.iter().cloned());
+            system.run_now(&world);
+        }
+
+        let recording = CommandRecording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            recording.commands,
+            vec![
+                RecordedCommand {
+                    time: 1.0,
+                    command: DebugCommand::TogglePause,
+                },
+                RecordedCommand {
+                    time: 3.0,
+                    command: DebugCommand::SpeedUp,
+                },
+                RecordedCommand {
+                    time: 3.0,
+                    command: DebugCommand::SlowDown,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn recorded_commands_play_back_at_their_recorded_times() {
+        let mut world = World::new();
+        let mut system = CommandPlaybackSystem::new(CommandRecording {
+            commands: vec![
+                RecordedCommand {
+                    time: 1.0,
+                    command: DebugCommand::TogglePause,
+                },
+                RecordedCommand {
+                    time: 2.5,
+                    command: DebugCommand::SpeedUp,
+                },
+            ],
+        });
+        System::setup(&mut system, &mut world);
+        let mut reader = world
+            .fetch_mut::<EventChannel<DebugCommand>>()
+            .register_reader();
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+
+        let mut played_back = Vec::new();
+        for _ in 0..3 {
+            system.run_now(&world);
+            let channel = world.fetch::<EventChannel<DebugCommand>>();
+            let commands: Vec<DebugCommand> = channel.read(&mut reader).cloned().collect();
+            played_back.push(commands);
+        }
+
+        assert_eq!(
+            played_back,
+            vec![
+                vec![DebugCommand::TogglePause],
+                vec![],
+                vec![DebugCommand::SpeedUp],
+            ]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use amethyst::{
+    ecs::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::creatures::{CreatureTag, TopplegrassTag},
+    resources::wind::{Wind, WindSpeedContributions},
+    systems::debug_input::DebugCommand,
+};
+
+/// Puts the simulation back in its initial state on a ResetSim command, without restarting the
+/// game: all creatures and topplegrass are deleted, and the wind is set back to how it was when
+/// the system was set up. The TopplegrassSpawnSystem restarts its spawn timer and spawns the
+/// initial population again on the same command. Anything else, such as the camera, is left
+/// alone.
+#[derive(Default)]
+pub struct ResetSystem {
+    command_reader_id: Option<ReaderId<DebugCommand>>,
+    initial_wind: Option<Wind>,
+}
+
+impl<'s> System<'s> for ResetSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, CreatureTag>,
+        ReadStorage<'s, TopplegrassTag>,
+        Write<'s, Wind>,
+        Write<'s, WindSpeedContributions>,
+        Read<'s, EventChannel<DebugCommand>>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.command_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<DebugCommand>>()
+                .register_reader(),
+        );
+        self.initial_wind = Some((*world.fetch::<Wind>()).clone());
+    }
+
+    fn run(
+        &mut self,
+        (entities, creature_tags, topple_tags, mut wind, mut speed_contributions, debug_commands): Self::SystemData,
+    ) {
+        let reset_requested = debug_commands
+            .read(self.command_reader_id.as_mut().unwrap())
+            .any(|command| *command == DebugCommand::ResetSim);
+        if !reset_requested {
+            return;
+        }
+        let mut deleted = 0;
+        for (entity, _) in (&entities, creature_tags.mask() | topple_tags.mask()).join() {
+            entities
+                .delete(entity)
+                .expect("Unable to delete entity during reset");
+            deleted += 1;
+        }
+        *wind = self.initial_wind.clone().unwrap_or_default();
+        *speed_contributions = WindSpeedContributions {
+            base: wind.wind.magnitude(),
+            ..Default::default()
+        };
+        info!("Reset the simulation, deleted {} entities", deleted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_clears_topplegrass_and_restores_the_wind() {
+        let mut world = World::new();
+        let mut reset = ResetSystem::default();
+        System::setup(&mut reset, &mut world);
+        for _ in 0..5 {
+            world.create_entity().with(TopplegrassTag).build();
+        }
+        let camera = world.create_entity().build();
+        world.write_resource::<Wind>().wind.x = 4.5;
+        world.write_resource::<WindSpeedContributions>().base = 4.5;
+
+        world
+            .write_resource::<EventChannel<DebugCommand>>()
+            .single_write(DebugCommand::ResetSim);
+        reset.run_now(&world);
+        world.maintain();
+
+        assert_eq!(world.read_storage::<TopplegrassTag>().join().count(), 0);
+        assert!(world.entities().is_alive(camera));
+        assert_eq!(world.read_resource::<Wind>().wind, Wind::default().wind);
+        assert_eq!(
+            world.read_resource::<WindSpeedContributions>().base,
+            Wind::default().wind.magnitude()
+        );
+    }
+}
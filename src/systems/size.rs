@@ -0,0 +1,64 @@
+use amethyst::{
+    core::{math::Vector3, transform::Transform},
+    ecs::*,
+};
+
+use crate::components::{collider::Circle, creatures::Size};
+
+/// Radius of the collider of a creature with a Size of 1.0.
+const COLLIDER_RADIUS_PER_SIZE: f32 = 1.125;
+
+/// Applies the Size of an entity to the scale of its Transform and the radius of its collider,
+/// so visuals and physics always agree on how big a creature is.
+pub struct SizeSystem;
+impl<'s> System<'s> for SizeSystem {
+    type SystemData = (
+        ReadStorage<'s, Size>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Circle>,
+    );
+
+    fn run(&mut self, (sizes, mut transforms, mut circles): Self::SystemData) {
+        for (size, transform) in (&sizes, &mut transforms).join() {
+            transform.set_scale(Vector3::new(size.value, size.value, size.value));
+        }
+        for (size, circle) in (&sizes, &mut circles).join() {
+            circle.radius = size.value * COLLIDER_RADIUS_PER_SIZE;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changing_size_updates_scale_and_collider_radius() {
+        let mut world = World::new();
+        let mut system = SizeSystem;
+        System::setup(&mut system, &mut world);
+        let entity = world
+            .create_entity()
+            .with(Transform::default())
+            .with(Circle { radius: 0.0 })
+            .with(Size { value: 0.4 })
+            .build();
+        system.run_now(&world);
+
+        world
+            .write_storage::<Size>()
+            .insert(entity, Size { value: 2.0 })
+            .unwrap();
+        system.run_now(&world);
+
+        let transforms = world.read_storage::<Transform>();
+        let circles = world.read_storage::<Circle>();
+        assert_eq!(
+            *transforms.get(entity).unwrap().scale(),
+            Vector3::new(2.0, 2.0, 2.0)
+        );
+        assert!(
+            (circles.get(entity).unwrap().radius - 2.0 * COLLIDER_RADIUS_PER_SIZE).abs() < 1e-6
+        );
+    }
+}
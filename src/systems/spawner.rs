@@ -9,9 +9,13 @@ use rand::{
     thread_rng, Rng,
 };
 
+use std::collections::VecDeque;
 use std::f32::consts::PI;
 
-use crate::{components::creatures::CreatureType, resources::prefabs::CreaturePrefabs};
+use crate::{
+    components::creatures::{CreatureType, Size, Species},
+    resources::{prefabs::CreaturePrefabs, spawn_budget::SpawnBudget, spawn_stats::SpawnStats},
+};
 
 #[derive(Debug, Clone)]
 pub struct CreatureSpawnEvent {
@@ -39,9 +43,14 @@ impl Distribution<CreatureTypeDistribution> for Standard {
     }
 }
 
+/// Attaches the prefab of the creature type, and a Species component naming that type, to every
+/// entity for which a CreatureSpawnEvent is received. At most SpawnBudget::max_per_frame events
+/// are handled per frame; the rest wait in a queue for the next frames. Until then, their
+/// entities only have the components their emitter gave them.
 #[derive(Default)]
 pub struct CreatureSpawnerSystem {
     spawn_reader_id: Option<ReaderId<CreatureSpawnEvent>>,
+    pending: VecDeque<CreatureSpawnEvent>,
 }
 
 impl<'s> System<'s> for CreatureSpawnerSystem {
@@ -50,6 +59,9 @@ impl<'s> System<'s> for CreatureSpawnerSystem {
         Read<'s, EventChannel<CreatureSpawnEvent>>,
         Read<'s, CreaturePrefabs>,
         Write<'s, LazyUpdate>,
+        Write<'s, SpawnStats>,
+        Read<'s, SpawnBudget>,
+        WriteStorage<'s, Species>,
     );
 
     fn setup(&mut self, world: &mut World) {
@@ -61,8 +73,25 @@ impl<'s> System<'s> for CreatureSpawnerSystem {
         );
     }
 
-    fn run(&mut self, (_entities, spawn_events, prefabs, lazy_update): Self::SystemData) {
-        for event in spawn_events.read(self.spawn_reader_id.as_mut().unwrap()) {
+    fn run(
+        &mut self,
+        (entities, spawn_events, prefabs, lazy_update, mut spawn_stats, budget, mut species): Self::SystemData,
+    ) {
+        self.pending.extend(
+            spawn_events
+                .read(self.spawn_reader_id.as_mut().unwrap())
+                .cloned(),
+        );
+        let count = budget.max_per_frame.min(self.pending.len());
+        for event in self.pending.drain(..count) {
+            // The entity may have been deleted while its event was waiting in the queue.
+            if !entities.is_alive(event.entity) {
+                continue;
+            }
+            spawn_stats.record_spawn();
+            species
+                .insert(event.entity, Species(event.creature_type.clone()))
+                .expect("Unable to add species to spawned creature");
             if let Some(creature_prefab) = prefabs.get_prefab(&event.creature_type) {
                 lazy_update.insert(event.entity, creature_prefab.clone());
             }
@@ -101,6 +130,7 @@ impl<'s> System<'s> for DebugSpawnTriggerSystem {
                 rand::random();
             if creature_type == "Carnivore" || creature_type == "Herbivore" {
                 transform.set_scale(Vector3::new(0.4, 0.4, 0.4));
+                creature_entity_builder = creature_entity_builder.with(Size { value: 0.4 });
             }
             if creature_type == "Plant" {
                 let scale = rng.gen_range(0.8f32, 1.2f32);
@@ -117,3 +147,36 @@ impl<'s> System<'s> for DebugSpawnTriggerSystem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawns_beyond_the_budget_are_deferred() {
+        let mut world = World::new();
+        let mut system = CreatureSpawnerSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(SpawnBudget { max_per_frame: 10 });
+        for _ in 0..100 {
+            let entity = world.create_entity().build();
+            world
+                .write_resource::<EventChannel<CreatureSpawnEvent>>()
+                .single_write(CreatureSpawnEvent {
+                    creature_type: "Herbivore".to_string(),
+                    entity,
+                });
+        }
+
+        for frame in 1..=10 {
+            system.run_now(&world);
+            world.maintain();
+            assert_eq!(
+                world.read_resource::<SpawnStats>().total_spawned,
+                frame * 10
+            );
+        }
+        system.run_now(&world);
+        assert_eq!(world.read_resource::<SpawnStats>().total_spawned, 100);
+    }
+}
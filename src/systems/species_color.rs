@@ -0,0 +1,80 @@
+use amethyst::{
+    ecs::*,
+    renderer::resources::Tint,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{resources::species_color::SpeciesColor, systems::spawner::CreatureSpawnEvent};
+
+/// Tints every newly spawned creature with the color of its species, as configured in the
+/// SpeciesColor resource.
+#[derive(Default)]
+pub struct SpeciesColorSystem {
+    spawn_reader_id: Option<ReaderId<CreatureSpawnEvent>>,
+}
+
+impl<'s> System<'s> for SpeciesColorSystem {
+    type SystemData = (
+        Read<'s, EventChannel<CreatureSpawnEvent>>,
+        Read<'s, SpeciesColor>,
+        WriteStorage<'s, Tint>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        <Self as System<'_>>::SystemData::setup(world);
+        self.spawn_reader_id = Some(
+            world
+                .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+                .register_reader(),
+        );
+    }
+
+    fn run(&mut self, (spawn_events, species_color, mut tints): Self::SystemData) {
+        for event in spawn_events.read(self.spawn_reader_id.as_mut().unwrap()) {
+            if let Some(color) = species_color.color(&event.creature_type) {
+                tints
+                    .insert(event.entity, Tint(color))
+                    .expect("Unable to add tint to spawned creature");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amethyst::renderer::palette::Srgba;
+
+    #[test]
+    fn spawned_creature_gets_the_color_of_its_species() {
+        let mut world = World::new();
+        let mut system = SpeciesColorSystem::default();
+        System::setup(&mut system, &mut world);
+        world
+            .write_resource::<SpeciesColor>()
+            .colors
+            .insert("Herbivore".to_string(), [0.1, 0.2, 0.3, 1.0]);
+        let herbivore = world.create_entity().build();
+        let plant = world.create_entity().build();
+        {
+            let mut spawn_events = world.write_resource::<EventChannel<CreatureSpawnEvent>>();
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Herbivore".to_string(),
+                entity: herbivore,
+            });
+            spawn_events.single_write(CreatureSpawnEvent {
+                creature_type: "Plant".to_string(),
+                entity: plant,
+            });
+        }
+
+        system.run_now(&world);
+
+        let tints = world.read_storage::<Tint>();
+        assert_eq!(
+            tints.get(herbivore).unwrap().0,
+            Srgba::new(0.1, 0.2, 0.3, 1.0)
+        );
+        assert!(tints.get(plant).is_none());
+    }
+}
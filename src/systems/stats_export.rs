@@ -0,0 +1,179 @@
+use amethyst::{core::Time, core::Transform, ecs::*};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write as IoWrite},
+};
+
+use crate::{
+    components::{
+        creatures::{CreatureTag, Movement},
+        digestion::Fullness,
+    },
+    resources::stats_export::StatsExportConfig,
+};
+
+pub const CSV_HEADER: &str = "time,id,x,y,speed,energy,age";
+
+/// Every StatsExportConfig::interval seconds, appends a row with the stats of every creature to
+/// the configured CSV file. Rows are buffered and written out once per sample.
+/// The age of a creature is the time since this system first saw it. Only active when
+/// StatsExportConfig::enabled is set.
+#[derive(Default)]
+pub struct CreatureStatsExportSystem {
+    writer: Option<BufWriter<File>>,
+    failed: bool,
+    elapsed: f64,
+    next_sample: f64,
+    first_seen: HashMap<Entity, f64>,
+}
+
+impl<'s> System<'s> for CreatureStatsExportSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, CreatureTag>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, Movement>,
+        ReadStorage<'s, Fullness>,
+        Read<'s, Time>,
+        Read<'s, StatsExportConfig>,
+    );
+
+    fn run(
+        &mut self,
+        (entities, creature_tags, transforms, movements, fullnesses, time, config): Self::SystemData,
+    ) {
+        if !config.enabled || self.failed {
+            return;
+        }
+        self.elapsed += f64::from(time.delta_seconds());
+        for (entity, _) in (&entities, &creature_tags).join() {
+            let elapsed = self.elapsed;
+            self.first_seen.entry(entity).or_insert(elapsed);
+        }
+        if self.elapsed < self.next_sample {
+            return;
+        }
+        self.next_sample = self.elapsed + f64::from(config.interval);
+        self.first_seen
+            .retain(|entity, _| entities.is_alive(*entity));
+
+        let mut rows = Vec::new();
+        for (entity, _, transform, movement, fullness) in (
+            &entities,
+            &creature_tags,
+            &transforms,
+            movements.maybe(),
+            fullnesses.maybe(),
+        )
+            .join()
+        {
+            let translation = transform.translation();
+            rows.push(CreatureStats {
+                id: entity.id(),
+                x: translation.x,
+                y: translation.y,
+                speed: movement.map_or(0.0, |movement| movement.velocity.magnitude()),
+                energy: fullness.map(|fullness| fullness.value),
+                age: self.elapsed
+                    - self
+                        .first_seen
+                        .get(&entity)
+                        .cloned()
+                        .unwrap_or(self.elapsed),
+            });
+        }
+        if let Err(error) = self.write_sample(&config.output_path, &rows) {
+            error!(
+                "Failed to write creature stats to {}. Stopping the export. Error: {:?}",
+                config.output_path, error
+            );
+            self.failed = true;
+        }
+    }
+}
+
+impl CreatureStatsExportSystem {
+    fn write_sample(&mut self, path: &str, rows: &[CreatureStats]) -> io::Result<()> {
+        if self.writer.is_none() {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writeln!(writer, "{}", CSV_HEADER)?;
+            self.writer = Some(writer);
+        }
+        let writer = self.writer.as_mut().unwrap();
+        for row in rows {
+            write_row(writer, self.elapsed, row)?;
+        }
+        writer.flush()
+    }
+}
+
+/// The stats of a single creature at the time of a sample.
+struct CreatureStats {
+    id: u32,
+    x: f32,
+    y: f32,
+    speed: f32,
+    /// None for creatures that don't eat.
+    energy: Option<f32>,
+    age: f64,
+}
+
+fn write_row<W: IoWrite>(writer: &mut W, time: f64, stats: &CreatureStats) -> io::Result<()> {
+    let energy = stats
+        .energy
+        .map_or(String::new(), |energy| energy.to_string());
+    writeln!(
+        writer,
+        "{},{},{},{},{},{},{}",
+        time, stats.id, stats.x, stats.y, stats.speed, energy, stats.age
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn writes_header_and_a_row_per_creature() {
+        let path = std::env::temp_dir().join(format!(
+            "evoli_{}_writes_header_and_a_row_per_creature.csv",
+            std::process::id()
+        ));
+        let mut world = World::new();
+        let mut system = CreatureStatsExportSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(StatsExportConfig {
+            enabled: true,
+            output_path: path.to_string_lossy().to_string(),
+            interval: 1.0,
+        });
+        world.write_resource::<Time>().set_delta_seconds(0.5);
+        world
+            .create_entity()
+            .with(CreatureTag)
+            .with(Transform::default())
+            .with(Fullness {
+                max: 100.0,
+                value: 42.0,
+            })
+            .build();
+        world
+            .create_entity()
+            .with(CreatureTag)
+            .with(Transform::default())
+            .build();
+        // Not a creature, so it should not be exported.
+        world.create_entity().with(Transform::default()).build();
+
+        system.run_now(&world);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let lines = contents.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().any(|line| line.contains(",42,")));
+    }
+}
@@ -0,0 +1,109 @@
+use amethyst::{
+    core::{math::Vector2, timing::Time, transform::Transform},
+    ecs::*,
+    shrev::EventChannel,
+};
+
+use crate::{
+    resources::{
+        timeline::{Timeline, TimelineEvent},
+        wind::WindSpeedContributions,
+    },
+    systems::spawner::CreatureSpawnEvent,
+};
+
+/// Executes the events of the Timeline resource once the simulated time reaches them.
+#[derive(Default)]
+pub struct TimelineSystem {
+    elapsed: f32,
+}
+
+impl<'s> System<'s> for TimelineSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        Write<'s, Timeline>,
+        Write<'s, WindSpeedContributions>,
+        Read<'s, Time>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            lazy_update,
+            mut spawn_events,
+            mut timeline,
+            mut speed_contributions,
+            time,
+        ): Self::SystemData,
+    ) {
+        self.elapsed += time.delta_seconds();
+        for entry in timeline.entries.iter_mut() {
+            if entry.fired || entry.time > self.elapsed {
+                continue;
+            }
+            entry.fired = true;
+            match &entry.event {
+                TimelineEvent::SpawnCreature {
+                    creature_type,
+                    x,
+                    y,
+                } => {
+                    let mut transform = Transform::default();
+                    transform.set_translation_xyz(*x, *y, 0.02);
+                    let entity = lazy_update.create_entity(&entities).with(transform).build();
+                    spawn_events.single_write(CreatureSpawnEvent {
+                        creature_type: creature_type.clone(),
+                        entity,
+                    });
+                }
+                TimelineEvent::SetWind { x, y } => {
+                    // The WindMagnitudeControllerSystem turns the wind and eases it to the speed.
+                    let target = Vector2::new(*x, *y);
+                    if target.magnitude() > std::f32::EPSILON {
+                        speed_contributions.direction = Some(target.normalize());
+                    }
+                    speed_contributions.base = target.magnitude();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::timeline::TimelineEntry;
+
+    #[test]
+    fn event_fires_once_when_its_time_is_reached() {
+        let mut world = World::new();
+        let mut system = TimelineSystem::default();
+        System::setup(&mut system, &mut world);
+        world.insert(Timeline {
+            entries: vec![TimelineEntry::new(
+                3.0,
+                TimelineEvent::SpawnCreature {
+                    creature_type: "Herbivore".to_string(),
+                    x: 1.0,
+                    y: 2.0,
+                },
+            )],
+        });
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        world.write_resource::<Time>().set_delta_seconds(1.0);
+
+        let mut spawned = Vec::new();
+        for _ in 0..5 {
+            system.run_now(&world);
+            let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+            spawned.push(spawn_events.read(&mut reader).count());
+        }
+
+        assert_eq!(spawned, vec![0, 0, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,96 @@
+use amethyst::{core::transform::Transform, ecs::*, shrev::EventChannel};
+
+use std::f32::consts::PI;
+
+use crate::{resources::world_init::WorldInit, systems::spawner::CreatureSpawnEvent};
+
+/// Height at which the creatures of the WorldInit are placed.
+const HEIGHT: f32 = 0.02;
+
+/// Spawns the creatures listed in the WorldInit resource on its first run, and does nothing
+/// afterwards.
+#[derive(Default)]
+pub struct WorldInitSystem {
+    done: bool,
+}
+
+impl<'s> System<'s> for WorldInitSystem {
+    type SystemData = (
+        Entities<'s>,
+        Read<'s, LazyUpdate>,
+        Write<'s, EventChannel<CreatureSpawnEvent>>,
+        Read<'s, WorldInit>,
+    );
+
+    fn run(&mut self, (entities, lazy_update, mut spawn_events, world_init): Self::SystemData) {
+        if self.done {
+            return;
+        }
+        self.done = true;
+        for entry in &world_init.entries {
+            for index in 0..entry.count {
+                let angle = 2.0 * PI * index as f32 / entry.count as f32;
+                let mut transform = Transform::default();
+                transform.set_translation_xyz(
+                    entry.x + entry.spread * angle.cos(),
+                    entry.y + entry.spread * angle.sin(),
+                    HEIGHT,
+                );
+                let entity = lazy_update.create_entity(&entities).with(transform).build();
+                spawn_events.single_write(CreatureSpawnEvent {
+                    creature_type: entry.creature_type.clone(),
+                    entity,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::world_init::WorldInitEntry;
+
+    #[test]
+    fn configured_creatures_are_spawned_exactly_once() {
+        let mut world = World::new();
+        let mut system = WorldInitSystem::default();
+        System::setup(&mut system, &mut world);
+        world.register::<Transform>();
+        world.insert(WorldInit {
+            entries: vec![
+                WorldInitEntry {
+                    creature_type: "Herbivore".to_string(),
+                    x: 0.0,
+                    y: 0.0,
+                    count: 3,
+                    spread: 2.0,
+                },
+                WorldInitEntry {
+                    creature_type: "Carnivore".to_string(),
+                    x: 5.0,
+                    y: 5.0,
+                    count: 2,
+                    spread: 0.0,
+                },
+            ],
+        });
+        let mut reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+
+        for _ in 0..3 {
+            system.run_now(&world);
+            world.maintain();
+        }
+
+        let spawn_events = world.fetch::<EventChannel<CreatureSpawnEvent>>();
+        let types = spawn_events
+            .read(&mut reader)
+            .map(|event| event.creature_type.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(types.iter().filter(|t| *t == "Herbivore").count(), 3);
+        assert_eq!(types.iter().filter(|t| *t == "Carnivore").count(), 2);
+        assert_eq!(types.len(), 5);
+    }
+}
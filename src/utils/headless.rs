@@ -0,0 +1,227 @@
+use amethyst::{
+    core::{math::Vector3, Time},
+    ecs::prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+    components::{
+        collider::Circle,
+        creatures::{DespawnWhenOutOfBoundsTag, Movement, TopplegrassTag},
+        idle_timer::IdleTimer,
+        spawn_animation::SpawnAnimation,
+    },
+    resources::{game_rng::GameRng, world_bounds::WorldBounds},
+    systems::{
+        gravity::GravitySystem, movement::GlobalSpeedLimitSystem, movement::MovementSystem,
+        out_of_bounds::OutOfBoundsDespawnSystem, spawner::CreatureSpawnEvent,
+        topplegrass::SpawnVelocitySystem, topplegrass::TopplegrassSpawnSystem,
+        topplegrass::TopplingSystem, wind_force::WindForceSystem,
+    },
+};
+
+/// The core simulation systems running on a World of their own, without a window, renderer or
+/// any of the rest of the Amethyst game loop. Used by tests and benchmarks.
+/// Prefabs are not loaded, so after every step the simulation adds the components of the
+/// topplegrass prefab to the topplegrass that spawned during that step.
+/// The GameRng is seeded, so every HeadlessSimulation plays out the same way.
+pub struct HeadlessSimulation {
+    pub world: World,
+    dispatcher: Dispatcher<'static, 'static>,
+    spawn_reader: ReaderId<CreatureSpawnEvent>,
+}
+
+impl HeadlessSimulation {
+    pub fn new() -> HeadlessSimulation {
+        HeadlessSimulation::with_bounds(WorldBounds::new(-10.0, 10.0, -10.0, 10.0))
+    }
+
+    pub fn with_bounds(bounds: WorldBounds) -> HeadlessSimulation {
+        HeadlessSimulation::with_seed(bounds, 0)
+    }
+
+    /// Two simulations with the same bounds and seed play out the same way, different seeds give
+    /// different spawn locations and jumps.
+    pub fn with_seed(bounds: WorldBounds, seed: u64) -> HeadlessSimulation {
+        let mut world = World::new();
+        world.insert(bounds);
+        world.insert(GameRng::seeded(seed));
+        // The spawner adds these lazily, and none of the systems below read them.
+        world.register::<Circle>();
+        world.register::<IdleTimer>();
+        world.register::<SpawnAnimation>();
+        let mut dispatcher = DispatcherBuilder::new()
+            .with(
+                TopplegrassSpawnSystem::default(),
+                "topplegrass_spawn_system",
+                &[],
+            )
+            .with(
+                SpawnVelocitySystem::default(),
+                "spawn_velocity_system",
+                &["topplegrass_spawn_system"],
+            )
+            .with(
+                TopplingSystem::default(),
+                "toppling_system",
+                &["spawn_velocity_system"],
+            )
+            .with(
+                WindForceSystem::default(),
+                "wind_force_system",
+                &["toppling_system"],
+            )
+            .with(
+                GravitySystem::default(),
+                "gravity_system",
+                &["toppling_system"],
+            )
+            .with(
+                GlobalSpeedLimitSystem,
+                "global_speed_limit_system",
+                &["wind_force_system", "gravity_system"],
+            )
+            .with(
+                MovementSystem,
+                "movement_system",
+                &["global_speed_limit_system"],
+            )
+            .with(
+                OutOfBoundsDespawnSystem,
+                "out_of_bounds_despawn_system",
+                &["movement_system"],
+            )
+            .build();
+        dispatcher.setup(&mut world);
+        let spawn_reader = world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        HeadlessSimulation {
+            world,
+            dispatcher,
+            spawn_reader,
+        }
+    }
+
+    /// Advances the simulation by a single step of delta_seconds.
+    pub fn step(&mut self, delta_seconds: f32) {
+        self.world
+            .write_resource::<Time>()
+            .set_delta_seconds(delta_seconds);
+        self.dispatcher.dispatch(&self.world);
+        self.world.maintain();
+        self.stand_in_for_prefabs();
+    }
+
+    /// Adds the components the topplegrass prefab would add to the topplegrass spawned since the
+    /// last step.
+    fn stand_in_for_prefabs(&mut self) {
+        let spawned = self
+            .world
+            .fetch::<EventChannel<CreatureSpawnEvent>>()
+            .read(&mut self.spawn_reader)
+            .filter(|event| event.creature_type == "Topplegrass")
+            .map(|event| event.entity)
+            .collect::<Vec<Entity>>();
+        let mut movements = self.world.write_storage::<Movement>();
+        let mut topplegrass_tags = self.world.write_storage::<TopplegrassTag>();
+        let mut out_of_bounds_tags = self.world.write_storage::<DespawnWhenOutOfBoundsTag>();
+        for entity in spawned {
+            movements
+                .insert(
+                    entity,
+                    Movement {
+                        velocity: Vector3::zeros(),
+                        max_movement_speed: 10.0,
+                    },
+                )
+                .expect("Unable to add movement to topplegrass");
+            topplegrass_tags
+                .insert(entity, TopplegrassTag)
+                .expect("Unable to add tag to topplegrass");
+            out_of_bounds_tags
+                .insert(entity, DespawnWhenOutOfBoundsTag)
+                .expect("Unable to add out of bounds tag to topplegrass");
+        }
+    }
+}
+
+impl Default for HeadlessSimulation {
+    fn default() -> Self {
+        HeadlessSimulation::new()
+    }
+}
+
+/// Runs the core simulation systems for the given number of steps, each advancing the simulation
+/// by delta_seconds. Returns the World so the outcome can be inspected.
+pub fn run_headless(steps: usize, delta_seconds: f32) -> World {
+    let mut simulation = HeadlessSimulation::new();
+    for _ in 0..steps {
+        simulation.step(delta_seconds);
+    }
+    simulation.world
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        components::creatures::{DragProfile, Movement, TopplegrassTag},
+        resources::wind::Wind,
+        systems::spawner::CreatureSpawnEvent,
+    };
+    use amethyst::{core::Transform, shrev::EventChannel};
+
+    /// Blows the given wind over a world with the given bounds for the given number of ticks of
+    /// 1/60th of a second, and returns where the first topplegrass that spawned ended up.
+    pub fn wind_tunnel(wind: Wind, bounds: WorldBounds, ticks: usize) -> Transform {
+        let mut simulation = HeadlessSimulation::with_bounds(bounds);
+        simulation.world.insert(wind);
+        let mut spawn_reader = simulation
+            .world
+            .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+            .register_reader();
+        simulation.step(1.0 / 60.0);
+        let topplegrass = simulation
+            .world
+            .fetch::<EventChannel<CreatureSpawnEvent>>()
+            .read(&mut spawn_reader)
+            .next()
+            .expect("No topplegrass was spawned")
+            .entity;
+        for _ in 1..ticks {
+            simulation.step(1.0 / 60.0);
+        }
+        let transforms = simulation.world.read_storage::<Transform>();
+        transforms
+            .get(topplegrass)
+            .expect("The topplegrass has no transform")
+            .clone()
+    }
+
+    #[test]
+    fn headless_run_spawns_topplegrass() {
+        let world = run_headless(100, 1.0 / 60.0);
+        assert!(world.read_storage::<DragProfile>().join().count() > 0);
+    }
+
+    #[test]
+    fn spawned_topplegrass_gets_moving() {
+        let world = run_headless(100, 1.0 / 60.0);
+        let drag_profiles = world.read_storage::<DragProfile>();
+        let movements = world.read_storage::<Movement>();
+        let tags = world.read_storage::<TopplegrassTag>();
+        assert!((&drag_profiles, &movements, &tags)
+            .join()
+            .any(|(_, movement, _)| movement.velocity.norm() > 0.0));
+    }
+
+    #[test]
+    fn topplegrass_rolls_downwind() {
+        let bounds = WorldBounds::new(-20.0, 20.0, -5.0, 5.0);
+        let transform = wind_tunnel(Wind::new(3.0, 0.0), bounds, 120);
+        // It spawns on the left border, upwind.
+        assert!(transform.translation().x > -20.0 + 1.0);
+        assert!(transform.translation().y >= -5.0 && transform.translation().y <= 5.0);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod headless;
 pub mod hierarchy_util;
+pub mod scenario;
 pub mod spatial_hash;
+pub mod state_hash;
@@ -0,0 +1,162 @@
+use amethyst::{
+    ecs::prelude::*,
+    shrev::{EventChannel, ReaderId},
+};
+
+use std::collections::HashMap;
+use std::thread;
+
+use crate::{
+    resources::{topplegrass::TopplegrassConfig, wind::Wind, world_bounds::WorldBounds},
+    systems::spawner::CreatureSpawnEvent,
+    utils::headless::HeadlessSimulation,
+};
+
+/// The settings a balancing scenario runs with. Every run of the scenario uses the same settings,
+/// only the seed of the GameRng differs.
+#[derive(Clone)]
+pub struct ScenarioConfig {
+    pub bounds: WorldBounds,
+    pub wind: Wind,
+    pub topplegrass: TopplegrassConfig,
+    /// Duration of a single tick in seconds.
+    pub delta_seconds: f32,
+}
+
+impl Default for ScenarioConfig {
+    fn default() -> Self {
+        ScenarioConfig {
+            bounds: WorldBounds::new(-10.0, 10.0, -10.0, 10.0),
+            wind: Wind::default(),
+            topplegrass: TopplegrassConfig::default(),
+            delta_seconds: 1.0 / 60.0,
+        }
+    }
+}
+
+/// Summary of a single run of a scenario.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioResult {
+    pub seed: u64,
+    /// Number of entities that were spawned during the run.
+    pub spawned: usize,
+    /// Number of entities still alive at the end of the run.
+    pub final_population: usize,
+    /// Mean number of seconds the spawned entities lived. Entities still alive at the end of the
+    /// run count with their age at that point.
+    pub mean_lifetime: f32,
+}
+
+/// The results of a batch of runs of the same scenario, and their averages.
+#[derive(Clone, Debug)]
+pub struct BatchResult {
+    pub results: Vec<ScenarioResult>,
+    pub mean_final_population: f32,
+    pub mean_lifetime: f32,
+}
+
+/// Runs the scenario for the given number of ticks on a headless simulation seeded with seed.
+pub fn run_scenario(config: &ScenarioConfig, seed: u64, ticks: usize) -> ScenarioResult {
+    let mut simulation = HeadlessSimulation::with_seed(config.bounds.clone(), seed);
+    simulation.world.insert(config.wind.clone());
+    simulation.world.insert(config.topplegrass.clone());
+    let mut spawn_reader = simulation
+        .world
+        .fetch_mut::<EventChannel<CreatureSpawnEvent>>()
+        .register_reader();
+
+    let mut elapsed = 0.0;
+    let mut spawned = 0;
+    let mut spawn_times: HashMap<Entity, f32> = HashMap::new();
+    let mut lifetimes = Vec::new();
+    for _ in 0..ticks {
+        simulation.step(config.delta_seconds);
+        elapsed += config.delta_seconds;
+        for entity in read_spawned(&simulation.world, &mut spawn_reader) {
+            spawn_times.insert(entity, elapsed);
+            spawned += 1;
+        }
+        let entities = simulation.world.entities();
+        spawn_times.retain(|entity, spawn_time| {
+            if entities.is_alive(*entity) {
+                true
+            } else {
+                lifetimes.push(elapsed - *spawn_time);
+                false
+            }
+        });
+    }
+
+    let final_population = spawn_times.len();
+    lifetimes.extend(spawn_times.values().map(|spawn_time| elapsed - spawn_time));
+    ScenarioResult {
+        seed,
+        spawned,
+        final_population,
+        mean_lifetime: mean(&lifetimes),
+    }
+}
+
+/// Runs the scenario once for every seed, each on a thread of its own, and averages the results.
+pub fn run_batch(config: &ScenarioConfig, seeds: &[u64], ticks: usize) -> BatchResult {
+    let handles: Vec<_> = seeds
+        .iter()
+        .map(|&seed| {
+            let config = config.clone();
+            thread::spawn(move || run_scenario(&config, seed, ticks))
+        })
+        .collect();
+    let results: Vec<ScenarioResult> = handles
+        .into_iter()
+        .map(|handle| handle.join().expect("A scenario run panicked"))
+        .collect();
+    let populations: Vec<f32> = results
+        .iter()
+        .map(|result| result.final_population as f32)
+        .collect();
+    let lifetimes: Vec<f32> = results.iter().map(|result| result.mean_lifetime).collect();
+    BatchResult {
+        mean_final_population: mean(&populations),
+        mean_lifetime: mean(&lifetimes),
+        results,
+    }
+}
+
+fn read_spawned(world: &World, reader: &mut ReaderId<CreatureSpawnEvent>) -> Vec<Entity> {
+    world
+        .fetch::<EventChannel<CreatureSpawnEvent>>()
+        .read(reader)
+        .map(|event| event.entity)
+        .collect()
+}
+
+fn mean(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f32>() / values.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn different_seeds_give_different_results() {
+        let config = ScenarioConfig {
+            wind: Wind::new(3.0, 0.0),
+            topplegrass: TopplegrassConfig {
+                initial_population: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let batch = run_batch(&config, &[1, 2], 600);
+        assert_eq!(batch.results.len(), 2);
+        assert!(batch.results.iter().all(|result| result.spawned > 0));
+        // The initial population spawns at random points, so it takes a different amount of
+        // time to blow out of the world.
+        assert_ne!(batch.results[0], batch.results[1]);
+    }
+}
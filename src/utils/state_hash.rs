@@ -0,0 +1,142 @@
+use amethyst::{core::Transform, ecs::prelude::*};
+
+use crate::components::creatures::{DragProfile, Movement};
+
+/// Number of decimals that world_state_hash rounds every value to.
+pub const DEFAULT_HASH_DECIMALS: i32 = 3;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes the translation, rotation and velocity of all topplegrass in the world, each rounded to
+/// DEFAULT_HASH_DECIMALS decimals so tiny floating point differences don't change the hash.
+/// Running the same seeded simulation for the same number of fixed-length ticks always gives the
+/// same hash, so a test can pin it down to catch unintended changes in behavior.
+pub fn world_state_hash(world: &World) -> u64 {
+    world_state_hash_with_precision(world, DEFAULT_HASH_DECIMALS)
+}
+
+/// Like world_state_hash, but rounds every value to the given number of decimals. Fewer decimals
+/// make the hash less sensitive to small changes.
+/// Topplegrass is recognized by its DragProfile, which the spawner gives it even without a prefab,
+/// and is hashed in the order of the entity ids. Values are written to an FNV-1a hash rather than
+/// the standard library hasher, whose output may change between Rust releases.
+pub fn world_state_hash_with_precision(world: &World, decimals: i32) -> u64 {
+    let scale = 10f32.powi(decimals);
+    let drag_profiles = world.read_storage::<DragProfile>();
+    let transforms = world.read_storage::<Transform>();
+    let movements = world.read_storage::<Movement>();
+    let mut hash = FNV_OFFSET_BASIS;
+    for (_, transform, movement) in (&drag_profiles, &transforms, movements.maybe()).join() {
+        let rotation = transform.rotation().coords;
+        let velocity = movement.map_or([0.0; 3], |movement| {
+            [
+                movement.velocity.x,
+                movement.velocity.y,
+                movement.velocity.z,
+            ]
+        });
+        let translation = transform.translation();
+        let values = [
+            translation.x,
+            translation.y,
+            translation.z,
+            rotation.x,
+            rotation.y,
+            rotation.z,
+            rotation.w,
+            velocity[0],
+            velocity[1],
+            velocity[2],
+        ];
+        for value in values.iter() {
+            let rounded = (value * scale).round() as i64;
+            for byte in rounded.to_le_bytes().iter() {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        resources::{topplegrass::TopplegrassConfig, wind::Wind, world_bounds::WorldBounds},
+        utils::headless::HeadlessSimulation,
+    };
+
+    /// The hash of the simulation run by golden_simulation.
+    /// If a change in behavior is intended, run this test and replace the value with the hash
+    /// from the failure message.
+    const GOLDEN_HASH: u64 = 0x4c24_f5f0_b0c0_f16d;
+    const GOLDEN_SEED: u64 = 42;
+    const GOLDEN_TICKS: usize = 120;
+    /// A tick length that is a power of two keeps the elapsed time exact.
+    const GOLDEN_TICK: f32 = 1.0 / 64.0;
+
+    /// A HeadlessSimulation seeded with GOLDEN_SEED and an initial population of topplegrass
+    /// rolling in a steady wind, after GOLDEN_TICKS ticks of GOLDEN_TICK.
+    fn golden_simulation() -> HeadlessSimulation {
+        let bounds = WorldBounds::new(-10.0, 10.0, -10.0, 10.0);
+        let mut simulation = HeadlessSimulation::with_seed(bounds, GOLDEN_SEED);
+        simulation.world.insert(Wind::new(2.0, 1.0));
+        simulation.world.insert(TopplegrassConfig {
+            initial_population: 5,
+            ..Default::default()
+        });
+        for _ in 0..GOLDEN_TICKS {
+            simulation.step(GOLDEN_TICK);
+        }
+        simulation
+    }
+
+    #[test]
+    fn seeded_simulation_matches_the_golden_hash() {
+        let world = golden_simulation().world;
+        // The hash should cover topplegrass in motion, not only where it spawned.
+        let moving = (
+            &world.read_storage::<DragProfile>(),
+            &world.read_storage::<Movement>(),
+        )
+            .join()
+            .filter(|(_, movement)| movement.velocity.norm() > 0.0)
+            .count();
+        assert!(moving >= 5);
+        let hash = world_state_hash(&world);
+        assert_eq!(
+            hash, GOLDEN_HASH,
+            "The world state hash changed to {:#x}",
+            hash
+        );
+    }
+
+    #[test]
+    fn differences_below_the_precision_do_not_change_the_hash() {
+        let world = golden_simulation().world;
+        let before = world_state_hash(&world);
+        let precise_before = world_state_hash_with_precision(&world, 7);
+        // Far below half of the rounding step, so no value is pushed across a rounding boundary.
+        for transform in (&mut world.write_storage::<Transform>()).join() {
+            transform.prepend_translation_x(0.000_001);
+        }
+        assert_eq!(world_state_hash(&world), before);
+        assert_ne!(world_state_hash_with_precision(&world, 7), precise_before);
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_hash() {
+        let run = |seed| {
+            let bounds = WorldBounds::new(-10.0, 10.0, -10.0, 10.0);
+            let mut simulation = HeadlessSimulation::with_seed(bounds, seed);
+            for _ in 0..120 {
+                simulation.step(1.0 / 60.0);
+            }
+            world_state_hash(&simulation.world)
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+}